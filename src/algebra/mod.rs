@@ -3,7 +3,7 @@ use std::borrow::{Borrow, BorrowMut};
 
 
 
-use crate::coefficients::CoefficientField;
+use crate::coefficients::{CoefficientRing, CoefficientField};
 use crate::DegreeType;
 use crate::vector::Vector;
 
@@ -11,23 +11,93 @@ use crate::vector::Vector;
 
 
 
+/// Coefficients that can be shared with the thread pool used by the `parallel` feature.
+///
+/// With the feature, this requires `Send` and `Sync`, so that large dense tensor products can
+/// be computed on several threads. Otherwise every type satisfies it.
+#[cfg(all(feature = "parallel", not(feature = "deterministic")))]
+pub trait MaybeSendSync: Send + Sync {}
+
+#[cfg(all(feature = "parallel", not(feature = "deterministic")))]
+impl<T: Send + Sync> MaybeSendSync for T {}
+
+/// Coefficients that can be shared with the thread pool used by the `parallel` feature, which
+/// is any type without the feature.
+#[cfg(not(all(feature = "parallel", not(feature = "deterministic"))))]
+pub trait MaybeSendSync {}
+
+#[cfg(not(all(feature = "parallel", not(feature = "deterministic"))))]
+impl<T> MaybeSendSync for T {}
+
+
+/// A function applied by `Algebra` to each product of coefficients.
+///
+/// With the `parallel` feature, the function is called from several threads at once, so it
+/// must be `Fn`, `Send` and `Sync`.
+#[cfg(all(feature = "parallel", not(feature = "deterministic")))]
+pub trait ProductFn<S>: Fn(&S) -> S + Send + Sync {}
+
+#[cfg(all(feature = "parallel", not(feature = "deterministic")))]
+impl<S, F: Fn(&S) -> S + Send + Sync> ProductFn<S> for F {}
+
+/// A function applied by `Algebra` to each product of coefficients, which can be any `FnMut`
+/// without the `parallel` feature.
+#[cfg(not(all(feature = "parallel", not(feature = "deterministic"))))]
+pub trait ProductFn<S>: FnMut(&S) -> S {}
+
+#[cfg(not(all(feature = "parallel", not(feature = "deterministic"))))]
+impl<S, F: FnMut(&S) -> S> ProductFn<S> for F {}
+
+
+/// A vector space with a bilinear multiplication.
+///
+/// The two required methods accumulate each product of coefficients after applying `func` to
+/// it. This function must be linear.
 pub trait Algebra<'vec> : Vector<'vec> {
 
     fn multiply_and_add_into_impl(
         &mut self,
         lhs: impl Borrow<Self>,
         rhs: impl Borrow<Self>,
-        func: impl FnMut(&Self::ScalarType) -> Self::ScalarType,
+        func: impl ProductFn<Self::ScalarType>,
         to_degree: Option<DegreeType>
     );
 
     fn multiply_into_impl(
         &mut self,
         rhs: impl Borrow<Self>,
-        func: impl FnMut(&Self::ScalarType) -> Self::ScalarType,
+        func: impl ProductFn<Self::ScalarType>,
         to_degree: Option<DegreeType>
     );
 
+    /// Add the product of `lhs` and `rhs` to `self`, applying no function to the products.
+    ///
+    /// This is `multiply_and_add_into_impl` with the identity function, which is how it is
+    /// implemented by default. Implementations whose kernels are faster without a function to
+    /// call on each product override it.
+    fn multiply_and_add_into_unmapped_impl(
+        &mut self,
+        lhs: impl Borrow<Self>,
+        rhs: impl Borrow<Self>,
+        to_degree: Option<DegreeType>
+    )
+    {
+        self.multiply_and_add_into_impl(lhs, rhs, |v| { v.clone() }, to_degree);
+    }
+
+    /// Multiply `self` by `rhs` in place, applying no function to the products.
+    ///
+    /// As for `multiply_and_add_into_unmapped_impl`, this is `multiply_into_impl` with the
+    /// identity function by default.
+    fn multiply_into_unmapped_impl(
+        &mut self,
+        rhs: impl Borrow<Self>,
+        to_degree: Option<DegreeType>
+    )
+    {
+        self.multiply_into_impl(rhs, |v| { v.clone() }, to_degree);
+    }
+
     fn multiply(
         &self,
         rhs: impl Borrow<Self>,
@@ -35,7 +105,7 @@ pub trait Algebra<'vec> : Vector<'vec> {
     ) -> Self
     {
         let mut result = Self::new();
-        result.borrow_mut().multiply_and_add_into_impl(self, rhs.borrow(), |v| { v.clone() }, to_degree);
+        result.borrow_mut().multiply_and_add_into_unmapped_impl(self, rhs.borrow(), to_degree);
         result
     }
 
@@ -45,7 +115,7 @@ pub trait Algebra<'vec> : Vector<'vec> {
         to_degree: Option<DegreeType>
     ) -> &mut Self
     {
-        self.multiply_into_impl(rhs.borrow(), |v| { v.clone() }, to_degree);
+        self.multiply_into_unmapped_impl(rhs.borrow(), to_degree);
         self
     }
 
@@ -56,12 +126,7 @@ pub trait Algebra<'vec> : Vector<'vec> {
         to_degree: Option<DegreeType>
     ) -> &mut Self
     {
-        self.multiply_and_add_into_impl(
-            lhs.borrow(),
-            rhs.borrow(),
-            |v| { v.clone() },
-            to_degree
-        );
+        self.multiply_and_add_into_unmapped_impl(lhs.borrow(), rhs.borrow(), to_degree);
         self
     }
 
@@ -75,7 +140,7 @@ pub trait Algebra<'vec> : Vector<'vec> {
         self.multiply_and_add_into_impl(
             lhs.borrow(),
             rhs.borrow(),
-            <Self::ScalarType as CoefficientField>::uminus,
+            <Self::ScalarType as CoefficientRing>::uminus,
             to_degree);
         self
     }
@@ -100,11 +165,12 @@ pub trait Algebra<'vec> : Vector<'vec> {
         scalar: impl Into<Self::ScalarType>,
         to_degree: Option<DegreeType>
     ) -> &mut Self
+        where Self::ScalarType: MaybeSendSync
     {
         let sca = scalar.into();
         self.multiply_into_impl(
             rhs.borrow(),
-            move |v| { <Self::ScalarType as CoefficientField>::mul(&sca, v) },
+            move |v| { <Self::ScalarType as CoefficientRing>::mul(&sca, v) },
             to_degree
         );
         self
@@ -116,11 +182,12 @@ pub trait Algebra<'vec> : Vector<'vec> {
         scalar: impl Into<Self::ScalarType>,
         to_degree: Option<DegreeType>
     ) -> &mut Self
+        where Self::ScalarType: MaybeSendSync
     {
         let sca = scalar.into();
         self.multiply_into_impl(
             rhs.borrow(),
-            move |v| { <Self::ScalarType as CoefficientField>::mul(v, &sca) },
+            move |v| { <Self::ScalarType as CoefficientRing>::mul(v, &sca) },
             to_degree
         );
         self
@@ -132,6 +199,8 @@ pub trait Algebra<'vec> : Vector<'vec> {
         scalar: impl Into<<Self::ScalarType as CoefficientField>::RationalType>,
         to_degree: Option<DegreeType>
     ) -> &mut Self
+        where Self::ScalarType: CoefficientField,
+              <Self::ScalarType as CoefficientField>::RationalType: MaybeSendSync
     {
         let sca = scalar.into();
         self.multiply_into_impl(
@@ -145,14 +214,16 @@ pub trait Algebra<'vec> : Vector<'vec> {
     fn mul_rat_rdiv(
         &mut self,
         rhs: impl Borrow<Self>,
-        scalar: impl Into<Self::ScalarType>,
+        scalar: impl Into<<Self::ScalarType as CoefficientField>::RationalType>,
         to_degree: Option<DegreeType>
     ) -> &mut Self
+        where Self::ScalarType: CoefficientField,
+              <Self::ScalarType as CoefficientField>::RationalType: MaybeSendSync
     {
         let sca = scalar.into();
         self.multiply_into_impl(
             rhs.borrow(),
-            move |v| { <Self::ScalarType as CoefficientField>::mul(v, &sca) },
+            move |v| { <Self::ScalarType as CoefficientField>::div(v, &sca) },
             to_degree
         );
         self
@@ -1,7 +1,8 @@
 
-use std::cmp::{PartialEq, Ordering};
+use std::cmp::{self, PartialEq, Ordering};
 use std::convert::Into;
 use std::fmt::Display;
+use std::ops::{Bound, Range, RangeBounds};
 
 use crate::{DegreeType, DimensionType};
 
@@ -41,4 +42,31 @@ pub trait OrderedBasisWithDegree : OrderedBasis + BasisWithDegree
     fn index_to_degree(index: DimensionType) -> DegreeType;
 
     fn start_of_degree(deg: DegreeType) -> DimensionType;
+
+    /// Range of the indices of keys with degree in `degrees`, restricted to indices below
+    /// `dimension`.
+    fn degree_index_range(degrees: impl RangeBounds<DegreeType>, dimension: DimensionType) -> Range<DimensionType>
+    {
+        // Degrees above that of the last index start at or after dimension. They are clamped
+        // before calling start_of_degree, which overflows for large degrees.
+        let top = match dimension {
+            0 => 0,
+            dim => Self::index_to_degree(dim - 1) + 1
+        };
+        let start = |d: DegreeType| if d >= top { dimension } else { Self::start_of_degree(d) };
+
+        let begin = match degrees.start_bound() {
+            Bound::Included(d) => start(*d),
+            Bound::Excluded(d) => start(d.saturating_add(1)),
+            Bound::Unbounded => 0
+        };
+        let end = match degrees.end_bound() {
+            Bound::Included(d) => start(d.saturating_add(1)),
+            Bound::Excluded(d) => start(*d),
+            Bound::Unbounded => dimension
+        };
+
+        let begin = cmp::min(begin, dimension);
+        begin..cmp::max(cmp::min(end, dimension), begin)
+    }
 }
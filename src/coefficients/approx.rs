@@ -0,0 +1,243 @@
+use std::fmt::{self, Debug, Formatter};
+
+use num_bigint::BigInt;
+
+use super::{
+    CoefficientRing, CoefficientField, Complex, DoubleDouble, Dual, Interval, Matrix, ModP,
+    Polynomial, Rational,
+};
+
+
+/// Coefficients that can be compared up to an absolute tolerance.
+///
+/// Floating point computations rarely produce exact zeros, so comparing results with `==` or
+/// dropping zero coefficients from sparse vectors with `== S::zero()` is fragile. This trait
+/// provides comparisons that treat values within `tol` of each other as equal. Exact types, such
+/// as integers and rationals, ignore the tolerance and compare exactly.
+pub trait ApproxEq : CoefficientRing
+{
+    /// Tolerance used when none is given explicitly.
+    fn default_tolerance() -> f64;
+
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool;
+
+    fn is_approx_zero(&self, tol: f64) -> bool
+    {
+        self.approx_eq(&Self::zero(), tol)
+    }
+}
+
+
+/// Rule used to decide whether a coefficient is zero, and so can be dropped from sparse storage.
+///
+/// The exact policy only treats values equal to `S::zero()` as zero. The tolerance policy, which
+/// is available for types implementing `ApproxEq`, also treats values within the given tolerance
+/// of zero as zero.
+pub struct ZeroPolicy<S> {
+    tolerance: f64,
+    is_zero: fn(&S, f64) -> bool,
+}
+
+
+impl<S: CoefficientRing> ZeroPolicy<S> {
+
+    pub fn exact() -> Self
+    {
+        Self { tolerance: 0.0, is_zero: |v, _| *v == S::zero() }
+    }
+
+    pub fn is_zero(&self, val: &S) -> bool
+    {
+        (self.is_zero)(val, self.tolerance)
+    }
+
+    pub fn tolerance(&self) -> f64
+    {
+        self.tolerance
+    }
+}
+
+impl<S: ApproxEq> ZeroPolicy<S> {
+
+    pub fn with_tolerance(tol: f64) -> Self
+    {
+        Self { tolerance: tol, is_zero: S::is_approx_zero }
+    }
+
+    pub fn default_tolerance() -> Self
+    {
+        Self::with_tolerance(S::default_tolerance())
+    }
+}
+
+impl<S: CoefficientRing> Default for ZeroPolicy<S> {
+    fn default() -> Self {
+        Self::exact()
+    }
+}
+
+// Derived implementations would require S: Copy
+impl<S> Clone for ZeroPolicy<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for ZeroPolicy<S> {}
+
+impl<S> Debug for ZeroPolicy<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZeroPolicy").field("tolerance", &self.tolerance).finish()
+    }
+}
+
+
+macro_rules! impl_approx_eq_float {
+    ($($t:ty => $tol:expr),*) => {$(
+        impl ApproxEq for $t {
+            fn default_tolerance() -> f64 {
+                $tol
+            }
+
+            #[inline(always)]
+            fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+                ((self - other).abs() as f64) <= tol
+            }
+        }
+    )*};
+}
+
+impl_approx_eq_float!(f32 => 1e-6, f64 => 1e-12);
+
+
+macro_rules! impl_approx_eq_exact {
+    ($($t:ty),*) => {$(
+        impl ApproxEq for $t {
+            fn default_tolerance() -> f64 {
+                0.0
+            }
+
+            #[inline(always)]
+            fn approx_eq(&self, other: &Self, _tol: f64) -> bool {
+                self == other
+            }
+        }
+    )*};
+}
+
+impl_approx_eq_exact!(i32, i64, i128, BigInt, Rational);
+
+impl<const P: u64> ApproxEq for ModP<P> {
+    fn default_tolerance() -> f64 {
+        0.0
+    }
+
+    fn approx_eq(&self, other: &Self, _tol: f64) -> bool {
+        self == other
+    }
+}
+
+
+macro_rules! impl_approx_eq_complex {
+    ($($t:ty => $tol:expr),*) => {$(
+        impl ApproxEq for Complex<$t> {
+            fn default_tolerance() -> f64 {
+                $tol
+            }
+
+            fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+                (self.sub(other).norm() as f64) <= tol
+            }
+        }
+    )*};
+}
+
+impl_approx_eq_complex!(f32 => 1e-6, f64 => 1e-12);
+
+
+impl ApproxEq for DoubleDouble {
+    fn default_tolerance() -> f64 {
+        1e-28
+    }
+
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        self.sub(other).abs().to_f64() <= tol
+    }
+}
+
+impl ApproxEq for Interval {
+    fn default_tolerance() -> f64 {
+        1e-12
+    }
+
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        (self.lower() - other.lower()).abs() <= tol && (self.upper() - other.upper()).abs() <= tol
+    }
+}
+
+impl<S: CoefficientField + ApproxEq, const N: usize> ApproxEq for Dual<S, N> {
+    fn default_tolerance() -> f64 {
+        S::default_tolerance()
+    }
+
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        self.value().approx_eq(other.value(), tol)
+            && self.derivatives().iter().zip(other.derivatives().iter()).all(|(l, r)| l.approx_eq(r, tol))
+    }
+}
+
+impl<S: CoefficientField + ApproxEq> ApproxEq for Polynomial<S> {
+    fn default_tolerance() -> f64 {
+        S::default_tolerance()
+    }
+
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        self.sub(other).iter().all(|(_, v)| v.is_approx_zero(tol))
+    }
+}
+
+impl<S: ApproxEq, const N: usize> ApproxEq for Matrix<S, N> {
+    fn default_tolerance() -> f64 {
+        S::default_tolerance()
+    }
+
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        self.rows().iter().flatten()
+            .zip(other.rows().iter().flatten())
+            .all(|(l, r)| l.approx_eq(r, tol))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_float_tolerance() {
+        let val = 0.1f64 + 0.2;
+
+        assert_ne!(val, 0.3);
+        assert!(val.approx_eq(&0.3, f64::default_tolerance()));
+        assert!(!val.approx_eq(&0.31, 1e-3));
+        assert!(1e-17f64.is_approx_zero(1e-12));
+    }
+
+    #[test]
+    fn test_exact_types_ignore_tolerance() {
+        assert!(!Rational::new(1, 1000).is_approx_zero(1.0));
+        assert!(Rational::new(2, 4).approx_eq(&Rational::new(1, 2), 0.0));
+    }
+
+    #[test]
+    fn test_zero_policy() {
+        let exact = ZeroPolicy::<f64>::exact();
+        let approx = ZeroPolicy::<f64>::with_tolerance(1e-10);
+
+        assert!(exact.is_zero(&0.0));
+        assert!(!exact.is_zero(&1e-17));
+        assert!(approx.is_zero(&-1e-17));
+        assert!(!approx.is_zero(&1e-9));
+    }
+}
@@ -0,0 +1,227 @@
+use std::borrow::Borrow;
+use std::fmt::{self, Display, Formatter};
+
+use num_complex::Complex as NumComplex;
+use num_traits::{Float, Num};
+
+use super::{CoefficientBase, Commutative, CoefficientRing, CoefficientField, FromDegreeType};
+use crate::DegreeType;
+
+
+/// Complex number with real and imaginary parts of type `T`.
+///
+/// This is a thin wrapper around `num_complex::Complex`. The wrapper is necessary because the
+/// coefficient traits require conversions from the small integer types, which cannot be
+/// implemented for a type defined in another crate. Conversions to and from the underlying
+/// `num_complex` type are provided, so values can be passed freely between the two.
+///
+/// Complex numbers implement `CoefficientField` with the real type as their rational type, so
+/// tensors can be divided by real scalars, as is needed for the exponential and logarithm.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex<T>(NumComplex<T>);
+
+
+impl<T> Complex<T> {
+
+    pub const fn new(re: T, im: T) -> Self
+    {
+        Self(NumComplex::new(re, im))
+    }
+
+    pub fn into_inner(self) -> NumComplex<T>
+    {
+        self.0
+    }
+}
+
+impl<T: Copy> Complex<T> {
+
+    pub fn re(&self) -> T
+    {
+        self.0.re
+    }
+
+    pub fn im(&self) -> T
+    {
+        self.0.im
+    }
+}
+
+impl<T: Float> Complex<T> {
+
+    /// The imaginary unit.
+    pub fn i() -> Self
+    {
+        Self(NumComplex::i())
+    }
+
+    pub fn conj(&self) -> Self
+    {
+        Self(self.0.conj())
+    }
+
+    /// Squared modulus, which avoids the square root needed for `norm`.
+    pub fn norm_sqr(&self) -> T
+    {
+        self.0.norm_sqr()
+    }
+
+    pub fn norm(&self) -> T
+    {
+        self.0.norm()
+    }
+}
+
+
+impl<T> From<NumComplex<T>> for Complex<T> {
+    fn from(val: NumComplex<T>) -> Self {
+        Self(val)
+    }
+}
+
+impl<T: Display + Num + PartialOrd + Clone + std::ops::Neg<Output=T>> Display for Complex<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+
+// f32 and f64 have identical implementations apart from the real type, so we generate them with
+// a macro.
+macro_rules! impl_complex_field {
+    ($($t:ty),*) => {$(
+
+        impl From<$t> for Complex<$t> {
+            fn from(val: $t) -> Self {
+                Self::new(val, 0.0)
+            }
+        }
+
+        impl From<i8> for Complex<$t> {
+            fn from(val: i8) -> Self {
+                Self::new(<$t>::from(val), 0.0)
+            }
+        }
+
+        impl From<i16> for Complex<$t> {
+            fn from(val: i16) -> Self {
+                Self::new(<$t>::from(val), 0.0)
+            }
+        }
+
+        impl CoefficientBase for Complex<$t> {}
+
+        impl Commutative for Complex<$t> {}
+
+        impl FromDegreeType for Complex<$t> {
+            fn from_degree(deg: &DegreeType) -> Self {
+                Self::new(<$t>::from_degree(deg), 0.0)
+            }
+        }
+
+        impl CoefficientRing for Complex<$t>
+        {
+            #[inline(always)]
+            fn zero() -> Self {
+                Self::new(0.0, 0.0)
+            }
+            #[inline(always)]
+            fn one() -> Self {
+                Self::new(1.0, 0.0)
+            }
+            #[inline(always)]
+            fn mone() -> Self {
+                Self::new(-1.0, 0.0)
+            }
+
+            #[inline(always)]
+            fn uminus(&self) -> Self {
+                Self(-self.0)
+            }
+
+            #[inline(always)]
+            fn add(&self, other: &Self) -> Self {
+                Self(self.0 + other.0)
+            }
+            #[inline(always)]
+            fn sub(&self, other: &Self) -> Self {
+                Self(self.0 - other.0)
+            }
+            #[inline(always)]
+            fn mul(&self, other: &Self) -> Self {
+                Self(self.0 * other.0)
+            }
+            #[inline(always)]
+            fn add_inplace(&mut self, other: &Self) -> &mut Self {
+                self.0 += other.0;
+                self
+            }
+            #[inline(always)]
+            fn sub_inplace(&mut self, other: &Self) -> &mut Self {
+                self.0 -= other.0;
+                self
+            }
+            #[inline(always)]
+            fn mul_inplace(&mut self, other: &Self) -> &mut Self {
+                self.0 *= other.0;
+                self
+            }
+        }
+
+        impl CoefficientField for Complex<$t>
+        {
+            type RationalType = $t;
+
+            #[inline(always)]
+            fn inv(arg: impl Borrow<Self::RationalType>) -> Self
+            {
+                Self::new(1.0 / arg.borrow(), 0.0)
+            }
+
+            #[inline(always)]
+            fn div(&self, other: &Self::RationalType) -> Self {
+                Self(self.0 / *other)
+            }
+            #[inline(always)]
+            fn div_inplace(&mut self, other: &Self::RationalType) -> &mut Self {
+                self.0 /= *other;
+                self
+            }
+        }
+    )*};
+}
+
+impl_complex_field!(f32, f64);
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_from_small_integers() {
+        assert_eq!(Complex::<f64>::from(3i8), Complex::new(3.0, 0.0));
+        assert_eq!(Complex::<f32>::from(-2i16), Complex::new(-2.0, 0.0));
+        assert_eq!(Complex::<f64>::from_degree(&4), Complex::new(4.0, 0.0));
+    }
+
+    #[test]
+    fn test_multiply_by_i() {
+        let mut z = Complex::new(1.0f64, 2.0);
+
+        z.mul_inplace(&Complex::<f64>::i());
+        assert_eq!(z, Complex::new(-2.0, 1.0));
+
+        z.mul_inplace(&z.conj());
+        assert_eq!(z, Complex::new(5.0, 0.0));
+    }
+
+    #[test]
+    fn test_divide_by_real() {
+        let z = Complex::new(3.0f32, -1.5);
+
+        assert_eq!(z.div(&1.5), Complex::new(2.0, -1.0));
+        assert_eq!(Complex::<f32>::inv(4.0f32), Complex::new(0.25, 0.0));
+    }
+}
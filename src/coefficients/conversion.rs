@@ -0,0 +1,159 @@
+use num_bigint::BigInt;
+
+use super::{CoefficientRing, CoefficientField, Complex, DoubleDouble, Dual, Interval, Rational};
+
+
+/// Conversion of a coefficient to another coefficient type, possibly losing precision.
+///
+/// The standard `From` trait cannot be used for this, because conversions such as `f64` to `f32`
+/// or rational to `f64` are lossy and so are not provided by the standard library, and we cannot
+/// implement `From` between types defined in other crates. Every coefficient type converts into
+/// itself.
+pub trait ToCoefficient<T> {
+
+    fn to_coefficient(&self) -> T;
+
+}
+
+
+impl<S: CoefficientRing> ToCoefficient<S> for S {
+    #[inline(always)]
+    fn to_coefficient(&self) -> S {
+        self.clone()
+    }
+}
+
+
+// Conversions that are plain numeric casts.
+macro_rules! impl_cast_conversion {
+    ($($from:ty => $to:ty),*) => {$(
+        impl ToCoefficient<$to> for $from {
+            #[inline(always)]
+            fn to_coefficient(&self) -> $to {
+                *self as $to
+            }
+        }
+    )*};
+}
+
+impl_cast_conversion!(
+    f32 => f64, f64 => f32,
+    i32 => f32, i32 => f64, i64 => f32, i64 => f64, i128 => f32, i128 => f64,
+    i32 => i64, i32 => i128, i64 => i128
+);
+
+
+// Conversions that go through an existing From implementation.
+macro_rules! impl_from_conversion {
+    ($($from:ty => $to:ty),*) => {$(
+        impl ToCoefficient<$to> for $from {
+            #[inline(always)]
+            fn to_coefficient(&self) -> $to {
+                <$to>::from(self.clone())
+            }
+        }
+    )*};
+}
+
+impl_from_conversion!(
+    i32 => Rational, i64 => Rational, BigInt => Rational,
+    i32 => BigInt, i64 => BigInt, i128 => BigInt,
+    f32 => Complex<f32>, f64 => Complex<f64>,
+    f32 => DoubleDouble, f64 => DoubleDouble, i32 => DoubleDouble, i64 => DoubleDouble,
+    f64 => Interval
+);
+
+
+impl ToCoefficient<f64> for Rational {
+    fn to_coefficient(&self) -> f64 {
+        self.to_f64()
+    }
+}
+
+impl ToCoefficient<f32> for Rational {
+    fn to_coefficient(&self) -> f32 {
+        self.to_f64() as f32
+    }
+}
+
+impl ToCoefficient<DoubleDouble> for Rational {
+    fn to_coefficient(&self) -> DoubleDouble {
+        // Dividing the nearest double-doubles to the numerator and denominator is accurate to
+        // double-double precision unless they are too large to represent
+        let numer = Rational::from_integer(self.numer().clone());
+        let denom = Rational::from_integer(self.denom().clone());
+        split_double_double(&numer).div(&split_double_double(&denom))
+    }
+}
+
+/// Nearest double-double to an integral rational, computed as the nearest `f64` plus the nearest
+/// `f64` to the remainder.
+fn split_double_double(val: &Rational) -> DoubleDouble
+{
+    let hi = val.to_f64();
+    match Rational::from_float(hi) {
+        Some(hi_exact) => DoubleDouble::new(hi, val.sub(&hi_exact).to_f64()),
+        None => DoubleDouble::from_f64(hi)
+    }
+}
+
+impl ToCoefficient<f64> for DoubleDouble {
+    fn to_coefficient(&self) -> f64 {
+        self.to_f64()
+    }
+}
+
+impl ToCoefficient<Complex<f64>> for Complex<f32> {
+    fn to_coefficient(&self) -> Complex<f64> {
+        Complex::new(self.re() as f64, self.im() as f64)
+    }
+}
+
+impl ToCoefficient<Complex<f32>> for Complex<f64> {
+    fn to_coefficient(&self) -> Complex<f32> {
+        Complex::new(self.re() as f32, self.im() as f32)
+    }
+}
+
+/// The midpoint of the interval is used as the point estimate.
+impl ToCoefficient<f64> for Interval {
+    fn to_coefficient(&self) -> f64 {
+        self.midpoint()
+    }
+}
+
+/// Conversion of a dual number to its primal value, discarding the derivatives.
+impl<S: CoefficientField, const N: usize> ToCoefficient<S> for Dual<S, N> {
+    fn to_coefficient(&self) -> S {
+        self.value().clone()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_numeric_conversions() {
+        assert_eq!(ToCoefficient::<f32>::to_coefficient(&0.5f64), 0.5f32);
+        assert_eq!(ToCoefficient::<f64>::to_coefficient(&Rational::new(1, 4)), 0.25);
+        assert_eq!(ToCoefficient::<Rational>::to_coefficient(&7i64), Rational::from(7i8));
+    }
+
+    #[test]
+    fn test_rational_to_double_double_is_accurate() {
+        let third: DoubleDouble = Rational::new(1, 3).to_coefficient();
+        let expected = DoubleDouble::from_rational(1i8, 3i8);
+
+        assert!(third.sub(&expected).abs().to_f64() < 1e-32);
+    }
+
+    #[test]
+    fn test_dual_to_primal() {
+        let x = Dual::<f64, 2>::variable(1.5, 1);
+
+        assert_eq!(ToCoefficient::<f64>::to_coefficient(&x), 1.5);
+    }
+}
@@ -0,0 +1,277 @@
+use std::borrow::Borrow;
+use std::fmt::{self, Display, Formatter};
+
+use super::{CoefficientBase, Commutative, CoefficientRing, CoefficientField, FromDegreeType};
+use crate::DegreeType;
+
+
+/// Extended precision floating point number represented as an unevaluated sum of two `f64`s.
+///
+/// The value is `hi + lo`, where `lo` is at most half a unit in the last place of `hi`. This
+/// gives about 106 bits of significand, roughly 32 decimal digits, while the exponent range is
+/// that of `f64`. Arithmetic uses error-free transformations, so it is much faster than
+/// arbitrary precision arithmetic but several times slower than plain `f64`.
+///
+/// The algorithms are those of the QD library by Hida, Li and Bailey, using the accurate
+/// ("IEEE") variants of addition and division.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct DoubleDouble {
+    hi: f64,
+    lo: f64,
+}
+
+
+/// Sum of `a` and `b` together with the rounding error, assuming `|a| >= |b|`.
+#[inline(always)]
+fn quick_two_sum(a: f64, b: f64) -> (f64, f64)
+{
+    let s = a + b;
+    (s, b - (s - a))
+}
+
+/// Sum of `a` and `b` together with the rounding error.
+#[inline(always)]
+fn two_sum(a: f64, b: f64) -> (f64, f64)
+{
+    let s = a + b;
+    let bb = s - a;
+    (s, (a - (s - bb)) + (b - bb))
+}
+
+/// Product of `a` and `b` together with the rounding error.
+#[inline(always)]
+fn two_prod(a: f64, b: f64) -> (f64, f64)
+{
+    let p = a * b;
+    (p, a.mul_add(b, -p))
+}
+
+
+impl DoubleDouble {
+
+    /// Double-double representing the exact sum `hi + lo`.
+    pub fn new(hi: f64, lo: f64) -> Self
+    {
+        let (hi, lo) = two_sum(hi, lo);
+        Self { hi, lo }
+    }
+
+    pub const fn from_f64(val: f64) -> Self
+    {
+        Self { hi: val, lo: 0.0 }
+    }
+
+    /// Leading component, which is the value rounded to the nearest `f64`.
+    pub fn hi(&self) -> f64
+    {
+        self.hi
+    }
+
+    /// Trailing component, holding the bits that do not fit in `hi`.
+    pub fn lo(&self) -> f64
+    {
+        self.lo
+    }
+
+    pub fn to_f64(&self) -> f64
+    {
+        self.hi + self.lo
+    }
+
+    pub fn abs(&self) -> Self
+    {
+        if self.hi < 0.0 { self.uminus() } else { *self }
+    }
+
+    #[inline(always)]
+    fn mul_f64(&self, other: f64) -> Self
+    {
+        let (p1, p2) = two_prod(self.hi, other);
+        let (hi, lo) = quick_two_sum(p1, p2 + self.lo * other);
+        Self { hi, lo }
+    }
+
+    fn div_dd(&self, other: &Self) -> Self
+    {
+        // Long division, computing three f64 digits of the quotient
+        let q1 = self.hi / other.hi;
+        let r = CoefficientRing::sub(self, &other.mul_f64(q1));
+
+        let q2 = r.hi / other.hi;
+        let r = CoefficientRing::sub(&r, &other.mul_f64(q2));
+
+        let q3 = r.hi / other.hi;
+        let (hi, lo) = quick_two_sum(q1, q2);
+
+        CoefficientRing::add(&Self { hi, lo }, &Self::from_f64(q3))
+    }
+}
+
+
+impl From<f64> for DoubleDouble {
+    fn from(val: f64) -> Self {
+        Self::from_f64(val)
+    }
+}
+
+impl From<f32> for DoubleDouble {
+    fn from(val: f32) -> Self {
+        Self::from_f64(f64::from(val))
+    }
+}
+
+impl From<i8> for DoubleDouble {
+    fn from(val: i8) -> Self {
+        Self::from_f64(f64::from(val))
+    }
+}
+
+impl From<i16> for DoubleDouble {
+    fn from(val: i16) -> Self {
+        Self::from_f64(f64::from(val))
+    }
+}
+
+impl From<i32> for DoubleDouble {
+    fn from(val: i32) -> Self {
+        Self::from_f64(f64::from(val))
+    }
+}
+
+impl From<i64> for DoubleDouble {
+    fn from(val: i64) -> Self {
+        // i64 values need up to 63 bits, so split off the part that f64 cannot hold
+        let hi = val as f64;
+        let lo = (val as i128 - hi as i128) as f64;
+        Self::new(hi, lo)
+    }
+}
+
+impl Display for DoubleDouble {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.lo == 0.0 {
+            Display::fmt(&self.hi, f)
+        } else {
+            write!(f, "{} + {:e}", self.hi, self.lo)
+        }
+    }
+}
+
+
+impl CoefficientBase for DoubleDouble {}
+
+impl Commutative for DoubleDouble {}
+
+impl FromDegreeType for DoubleDouble {
+    fn from_degree(deg: &DegreeType) -> Self {
+        Self::from_f64(*deg as f64)
+    }
+}
+
+impl CoefficientRing for DoubleDouble
+{
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::from_f64(0.0)
+    }
+    #[inline(always)]
+    fn one() -> Self {
+        Self::from_f64(1.0)
+    }
+    #[inline(always)]
+    fn mone() -> Self {
+        Self::from_f64(-1.0)
+    }
+
+    #[inline(always)]
+    fn uminus(&self) -> Self {
+        Self { hi: -self.hi, lo: -self.lo }
+    }
+
+    #[inline(always)]
+    fn add(&self, other: &Self) -> Self {
+        let (s1, s2) = two_sum(self.hi, other.hi);
+        let (t1, t2) = two_sum(self.lo, other.lo);
+        let (s1, s2) = quick_two_sum(s1, s2 + t1);
+        let (hi, lo) = quick_two_sum(s1, s2 + t2);
+        Self { hi, lo }
+    }
+    #[inline(always)]
+    fn sub(&self, other: &Self) -> Self {
+        self.add(&other.uminus())
+    }
+    #[inline(always)]
+    fn mul(&self, other: &Self) -> Self {
+        let (p1, p2) = two_prod(self.hi, other.hi);
+        let p2 = p2 + (self.hi * other.lo + self.lo * other.hi);
+        let (hi, lo) = quick_two_sum(p1, p2);
+        Self { hi, lo }
+    }
+    #[inline(always)]
+    fn add_inplace(&mut self, other: &Self) -> &mut Self {
+        *self = CoefficientRing::add(&*self, other);
+        self
+    }
+    #[inline(always)]
+    fn sub_inplace(&mut self, other: &Self) -> &mut Self {
+        *self = CoefficientRing::sub(&*self, other);
+        self
+    }
+    #[inline(always)]
+    fn mul_inplace(&mut self, other: &Self) -> &mut Self {
+        *self = CoefficientRing::mul(&*self, other);
+        self
+    }
+}
+
+impl CoefficientField for DoubleDouble
+{
+    type RationalType = DoubleDouble;
+
+    fn inv(arg: impl Borrow<Self::RationalType>) -> Self
+    {
+        Self::one().div_dd(arg.borrow())
+    }
+
+    fn div(&self, other: &Self::RationalType) -> Self {
+        self.div_dd(other)
+    }
+
+    fn div_inplace(&mut self, other: &Self::RationalType) -> &mut Self {
+        *self = self.div_dd(other);
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_recovers_bits_lost_by_f64() {
+        let big = DoubleDouble::from(1.0e20);
+        let sum = CoefficientRing::add(&big, &DoubleDouble::one());
+
+        assert_eq!(CoefficientRing::sub(&sum, &big), DoubleDouble::one());
+        assert_eq!(1.0e20f64 + 1.0 - 1.0e20, 0.0);
+    }
+
+    #[test]
+    fn test_third_times_three() {
+        let third = DoubleDouble::from_rational(1i8, 3i8);
+        let result = CoefficientRing::mul(&third, &DoubleDouble::from(3i8));
+        let err = CoefficientRing::sub(&result, &DoubleDouble::one());
+
+        assert!(third.lo() != 0.0);
+        assert!(err.abs().to_f64() < 1e-31);
+    }
+
+    #[test]
+    fn test_from_i64_is_exact() {
+        let val = DoubleDouble::from(i64::MAX);
+
+        assert_eq!(val.hi() as i128 + val.lo() as i128, i64::MAX as i128);
+    }
+}
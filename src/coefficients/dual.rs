@@ -0,0 +1,228 @@
+use std::borrow::Borrow;
+
+use super::{CoefficientBase, Commutative, CoefficientRing, CoefficientField, FromDegreeType};
+use crate::DegreeType;
+
+
+/// Dual number carrying a value together with its derivatives in `N` directions.
+///
+/// Arithmetic on dual numbers propagates first derivatives by the product rule, so running any
+/// computation with dual coefficients, such as the tensor exponential, computes the directional
+/// derivatives of every output coefficient alongside its value. This is forward-mode automatic
+/// differentiation. Each input is either a constant, whose derivatives vanish, or a variable
+/// seeded with a unit derivative in one direction.
+///
+/// Dual numbers over a field can be divided by elements of the rational type of that field,
+/// which is all that the tensor exponential and logarithm require.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dual<S: CoefficientField, const N: usize> {
+    value: S,
+    eps: [S; N],
+}
+
+
+impl<S: CoefficientField, const N: usize> Dual<S, N> {
+
+    pub fn new(value: S, eps: [S; N]) -> Self
+    {
+        Self { value, eps }
+    }
+
+    /// Dual number with the given value and all derivatives zero.
+    pub fn constant(value: S) -> Self
+    {
+        Self { value, eps: std::array::from_fn(|_| S::zero()) }
+    }
+
+    /// Independent variable with the given value and unit derivative in direction `direction`.
+    ///
+    /// Panics if `direction` is not less than `N`.
+    pub fn variable(value: S, direction: usize) -> Self
+    {
+        assert!(direction < N, "direction {} out of range for {} directions", direction, N);
+        let mut result = Self::constant(value);
+        result.eps[direction] = S::one();
+        result
+    }
+
+    pub fn value(&self) -> &S
+    {
+        &self.value
+    }
+
+    pub fn derivative(&self, direction: usize) -> &S
+    {
+        &self.eps[direction]
+    }
+
+    pub fn derivatives(&self) -> &[S; N]
+    {
+        &self.eps
+    }
+
+    pub fn into_parts(self) -> (S, [S; N])
+    {
+        (self.value, self.eps)
+    }
+}
+
+
+impl<S: CoefficientField, const N: usize> From<i8> for Dual<S, N> {
+    fn from(val: i8) -> Self {
+        Self::constant(S::from(val))
+    }
+}
+
+impl<S: CoefficientField, const N: usize> From<i16> for Dual<S, N> {
+    fn from(val: i16) -> Self {
+        Self::constant(S::from(val))
+    }
+}
+
+
+impl<S: CoefficientField, const N: usize> CoefficientBase for Dual<S, N> {}
+
+impl<S: CoefficientField + Commutative, const N: usize> Commutative for Dual<S, N> {}
+
+impl<S: CoefficientField, const N: usize> FromDegreeType for Dual<S, N> {
+    fn from_degree(deg: &DegreeType) -> Self {
+        Self::constant(S::from_degree(deg))
+    }
+}
+
+impl<S: CoefficientField, const N: usize> CoefficientRing for Dual<S, N>
+{
+    fn zero() -> Self {
+        Self::constant(S::zero())
+    }
+
+    fn one() -> Self {
+        Self::constant(S::one())
+    }
+
+    fn mone() -> Self {
+        Self::constant(S::mone())
+    }
+
+    fn uminus(&self) -> Self {
+        Self {
+            value: self.value.uminus(),
+            eps: std::array::from_fn(|i| self.eps[i].uminus()),
+        }
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Self {
+            value: self.value.add(&other.value),
+            eps: std::array::from_fn(|i| self.eps[i].add(&other.eps[i])),
+        }
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        Self {
+            value: self.value.sub(&other.value),
+            eps: std::array::from_fn(|i| self.eps[i].sub(&other.eps[i])),
+        }
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        // (a + a'e)(b + b'e) = ab + (a'b + ab')e
+        Self {
+            value: self.value.mul(&other.value),
+            eps: std::array::from_fn(|i| {
+                let mut d = self.eps[i].mul(&other.value);
+                d.add_inplace(&self.value.mul(&other.eps[i]));
+                d
+            }),
+        }
+    }
+
+    fn add_inplace(&mut self, other: &Self) -> &mut Self {
+        self.value.add_inplace(&other.value);
+        for (lhs, rhs) in self.eps.iter_mut().zip(other.eps.iter()) {
+            lhs.add_inplace(rhs);
+        }
+        self
+    }
+
+    fn sub_inplace(&mut self, other: &Self) -> &mut Self {
+        self.value.sub_inplace(&other.value);
+        for (lhs, rhs) in self.eps.iter_mut().zip(other.eps.iter()) {
+            lhs.sub_inplace(rhs);
+        }
+        self
+    }
+
+    fn mul_inplace(&mut self, other: &Self) -> &mut Self {
+        for (lhs, rhs) in self.eps.iter_mut().zip(other.eps.iter()) {
+            lhs.mul_inplace(&other.value);
+            lhs.add_inplace(&self.value.mul(rhs));
+        }
+        self.value.mul_inplace(&other.value);
+        self
+    }
+}
+
+impl<S: CoefficientField, const N: usize> CoefficientField for Dual<S, N>
+{
+    type RationalType = S::RationalType;
+
+    fn inv(arg: impl Borrow<Self::RationalType>) -> Self
+    {
+        Self::constant(S::inv(arg))
+    }
+
+    fn div(&self, other: &Self::RationalType) -> Self {
+        Self {
+            value: self.value.div(other),
+            eps: std::array::from_fn(|i| self.eps[i].div(other)),
+        }
+    }
+
+    fn div_inplace(&mut self, other: &Self::RationalType) -> &mut Self {
+        self.value.div_inplace(other);
+        for d in self.eps.iter_mut() {
+            d.div_inplace(other);
+        }
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_product_rule() {
+        let x = Dual::<f64, 2>::variable(3.0, 0);
+        let y = Dual::<f64, 2>::variable(-2.0, 1);
+
+        // d(x^2 y) = 2xy dx + x^2 dy
+        let result = x.mul(&x).mul(&y);
+
+        assert_eq!(*result.value(), -18.0);
+        assert_eq!(result.derivatives(), &[-12.0, 9.0]);
+    }
+
+    #[test]
+    fn test_mul_inplace_matches_mul() {
+        let x = Dual::<f64, 1>::new(1.5, [2.0]);
+        let y = Dual::<f64, 1>::new(-0.5, [4.0]);
+
+        let mut result = x.clone();
+        result.mul_inplace(&y);
+
+        assert_eq!(result, x.mul(&y));
+    }
+
+    #[test]
+    fn test_constants_have_zero_derivative() {
+        let c = Dual::<f64, 3>::from_degree(&5);
+
+        assert_eq!(*c.value(), 5.0);
+        assert_eq!(c.derivatives(), &[0.0; 3]);
+        assert_eq!(Dual::<f64, 3>::variable(1.0, 2).div(&4.0).derivatives(), &[0.0, 0.0, 0.25]);
+    }
+}
@@ -1,7 +1,7 @@
-
 use std::borrow::Borrow;
 
-use super::{CoefficientBase, Commutative, CoefficientField, FromDegreeType};
+use super::simd;
+use super::{CoefficientBase, Commutative, CoefficientRing, CoefficientField, FromDegreeType};
 use crate::DegreeType;
 
 impl CoefficientBase for f32 {}
@@ -23,22 +23,25 @@ impl FromDegreeType for f64 {
     }
 }
 
-impl CoefficientField for f32
+impl CoefficientRing for f32
 {
-    type RationalType = f32;
-    const ZERO: f32 = 0.0f32;
-    const ONE: f32 = 1.0f32;
-    const MONE: f32 = -1.0f32;
+    #[inline(always)]
+    fn zero() -> Self {
+        0.0f32
+    }
+    #[inline(always)]
+    fn one() -> Self {
+        1.0f32
+    }
+    #[inline(always)]
+    fn mone() -> Self {
+        -1.0f32
+    }
 
     #[inline(always)]
     fn uminus(&self) -> Self {
         -(*self)
     }
-    #[inline(always)]
-    fn inv(arg: impl Borrow<Self::RationalType>) -> Self
-    {
-        Self::ONE / arg.borrow()
-    }
 
     #[inline(always)]
     fn add(&self, other: &Self) -> Self {
@@ -53,10 +56,6 @@ impl CoefficientField for f32
         self * other
     }
     #[inline(always)]
-    fn div(&self, other: &Self::RationalType) -> Self {
-        self / other
-    }
-    #[inline(always)]
     fn add_inplace(&mut self, other: &Self) -> &mut Self {
         *self += other;
         self
@@ -72,6 +71,90 @@ impl CoefficientField for f32
         self
     }
     #[inline(always)]
+    fn lmul_inplace(&mut self, other: &Self) -> &mut Self {
+        *self *= other;
+        self
+    }
+
+    fn add_slice_inplace(out: &mut [Self], rhs: &[Self]) {
+        simd::f32_kernels::add_assign(out, rhs);
+    }
+    fn sub_slice_inplace(out: &mut [Self], rhs: &[Self]) {
+        simd::f32_kernels::sub_assign(out, rhs);
+    }
+    fn mul_slice_inplace(out: &mut [Self], scalar: &Self) {
+        simd::f32_kernels::scale(out, *scalar);
+    }
+    fn lmul_slice_inplace(out: &mut [Self], scalar: &Self) {
+        simd::f32_kernels::scale(out, *scalar);
+    }
+    fn add_mul_slice_inplace(out: &mut [Self], rhs: &[Self], scalar: &Self) {
+        simd::f32_kernels::axpy(out, *scalar, rhs);
+    }
+    fn add_lmul_slice_inplace(out: &mut [Self], rhs: &[Self], scalar: &Self) {
+        simd::f32_kernels::axpy(out, *scalar, rhs);
+    }
+
+    fn outer_product_add_inplace(out: &mut [Self], lhs: &[Self], rhs: &[Self])
+    {
+        if rhs.is_empty() {
+            return;
+        }
+        for (out_row, lhs_v) in out.chunks_mut(rhs.len()).zip(lhs) {
+            simd::f32_kernels::axpy(out_row, *lhs_v, rhs);
+        }
+    }
+
+    fn outer_product_map_add_inplace<F>(
+        out: &mut [Self],
+        lhs: &[Self],
+        rhs: &[Self],
+        func: &mut F,
+        buffer: &mut Vec<Self>,
+    )
+        where F: FnMut(&Self) -> Self
+    {
+        if rhs.is_empty() {
+            return;
+        }
+        // The products of each row are formed in the buffer, so that func sees every product
+        // exactly as the default implementation does.
+        for (out_row, lhs_v) in out.chunks_mut(rhs.len()).zip(lhs) {
+            buffer.clear();
+            buffer.extend_from_slice(rhs);
+            simd::f32_kernels::scale(buffer, *lhs_v);
+            for v in buffer.iter_mut() {
+                *v = func(v);
+            }
+            simd::f32_kernels::add_assign(out_row, buffer);
+        }
+    }
+
+    fn mul_map_slice_inplace<F>(out: &mut [Self], scalar: &Self, func: &mut F)
+        where F: FnMut(&Self) -> Self
+    {
+        simd::f32_kernels::scale(out, *scalar);
+        for o in out.iter_mut() {
+            *o = func(o);
+        }
+    }
+}
+
+impl CoefficientField for f32
+{
+    type RationalType = f32;
+
+    #[inline(always)]
+    fn inv(arg: impl Borrow<Self::RationalType>) -> Self
+    {
+        Self::one() / arg.borrow()
+    }
+
+    #[inline(always)]
+    fn div(&self, other: &Self::RationalType) -> Self {
+        self / other
+    }
+    #[inline(always)]
     fn div_inplace(&mut self, other: &Self::RationalType) -> &mut Self {
         *self /= other;
         self
@@ -79,22 +162,25 @@ impl CoefficientField for f32
 }
 
 
-impl CoefficientField for f64
+impl CoefficientRing for f64
 {
-    type RationalType = f64;
+    #[inline(always)]
+    fn zero() -> Self {
+        0.0f64
+    }
+    #[inline(always)]
+    fn one() -> Self {
+        1.0f64
+    }
+    #[inline(always)]
+    fn mone() -> Self {
+        -1.0f64
+    }
 
-    const ZERO: f64 = 0.0f64;
-    const ONE: f64 = 1.0f64;
-    const MONE: f64 = -1.0f64;
     #[inline(always)]
     fn uminus(&self) -> Self {
         -(*self)
     }
-    #[inline(always)]
-    fn inv(arg: impl Borrow<Self::RationalType>) -> Self
-    {
-        Self::ONE / arg.borrow()
-    }
 
     #[inline(always)]
     fn add(&self, other: &Self) -> Self {
@@ -109,10 +195,6 @@ impl CoefficientField for f64
         self * other
     }
     #[inline(always)]
-    fn div(&self, other: &Self::RationalType) -> Self {
-        self / other
-    }
-    #[inline(always)]
     fn add_inplace(&mut self, other: &Self) -> &mut Self {
         *self += other;
         self
@@ -128,8 +210,144 @@ impl CoefficientField for f64
         self
     }
     #[inline(always)]
+    fn lmul_inplace(&mut self, other: &Self) -> &mut Self {
+        *self *= other;
+        self
+    }
+
+    fn add_slice_inplace(out: &mut [Self], rhs: &[Self]) {
+        simd::f64_kernels::add_assign(out, rhs);
+    }
+    fn sub_slice_inplace(out: &mut [Self], rhs: &[Self]) {
+        simd::f64_kernels::sub_assign(out, rhs);
+    }
+    fn mul_slice_inplace(out: &mut [Self], scalar: &Self) {
+        simd::f64_kernels::scale(out, *scalar);
+    }
+    fn lmul_slice_inplace(out: &mut [Self], scalar: &Self) {
+        simd::f64_kernels::scale(out, *scalar);
+    }
+    fn add_mul_slice_inplace(out: &mut [Self], rhs: &[Self], scalar: &Self) {
+        simd::f64_kernels::axpy(out, *scalar, rhs);
+    }
+    fn add_lmul_slice_inplace(out: &mut [Self], rhs: &[Self], scalar: &Self) {
+        simd::f64_kernels::axpy(out, *scalar, rhs);
+    }
+
+    fn outer_product_add_inplace(out: &mut [Self], lhs: &[Self], rhs: &[Self])
+    {
+        if rhs.is_empty() {
+            return;
+        }
+        for (out_row, lhs_v) in out.chunks_mut(rhs.len()).zip(lhs) {
+            simd::f64_kernels::axpy(out_row, *lhs_v, rhs);
+        }
+    }
+
+    fn outer_product_map_add_inplace<F>(
+        out: &mut [Self],
+        lhs: &[Self],
+        rhs: &[Self],
+        func: &mut F,
+        buffer: &mut Vec<Self>,
+    )
+        where F: FnMut(&Self) -> Self
+    {
+        if rhs.is_empty() {
+            return;
+        }
+        // The products of each row are formed in the buffer, so that func sees every product
+        // exactly as the default implementation does.
+        for (out_row, lhs_v) in out.chunks_mut(rhs.len()).zip(lhs) {
+            buffer.clear();
+            buffer.extend_from_slice(rhs);
+            simd::f64_kernels::scale(buffer, *lhs_v);
+            for v in buffer.iter_mut() {
+                *v = func(v);
+            }
+            simd::f64_kernels::add_assign(out_row, buffer);
+        }
+    }
+
+    fn mul_map_slice_inplace<F>(out: &mut [Self], scalar: &Self, func: &mut F)
+        where F: FnMut(&Self) -> Self
+    {
+        simd::f64_kernels::scale(out, *scalar);
+        for o in out.iter_mut() {
+            *o = func(o);
+        }
+    }
+}
+
+impl CoefficientField for f64
+{
+    type RationalType = f64;
+
+    #[inline(always)]
+    fn inv(arg: impl Borrow<Self::RationalType>) -> Self
+    {
+        Self::one() / arg.borrow()
+    }
+
+    #[inline(always)]
+    fn div(&self, other: &Self::RationalType) -> Self {
+        self / other
+    }
+    #[inline(always)]
     fn div_inplace(&mut self, other: &Self::RationalType) -> &mut Self {
         *self /= other;
         self
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_mapped_kernels_apply_func_to_each_product() {
+        // Dividing by 3 does not commute with multiplication in floating point, so these only
+        // agree bit for bit if func is applied to each product.
+        let lhs: Vec<f64> = (0..5).map(|i| (i as f64 * 0.7).sin()).collect();
+        let rhs: Vec<f64> = (0..7).map(|i| (i as f64 * 1.3).cos()).collect();
+        let mut func = |v: &f64| v / 3.0;
+        // A buffer left over from an earlier call, longer than a row.
+        let mut buffer = vec![f64::NAN; 9];
+
+        let mut out = vec![1.0f64; 35];
+        f64::outer_product_map_add_inplace(&mut out, &lhs, &rhs, &mut func, &mut buffer);
+        let expected = lhs.iter().flat_map(|l| rhs.iter().map(move |r| 1.0 + (l * r) / 3.0));
+        assert!(out.iter().zip(expected).all(|(o, e)| o.to_bits() == e.to_bits()));
+
+        let mut out = lhs.clone();
+        f64::mul_map_slice_inplace(&mut out, &rhs[1], &mut func);
+        assert!(out.iter().zip(&lhs).all(|(o, l)| o.to_bits() == ((l * rhs[1]) / 3.0).to_bits()));
+
+        let lhs32: Vec<f32> = lhs.iter().map(|v| *v as f32).collect();
+        let rhs32: Vec<f32> = rhs.iter().map(|v| *v as f32).collect();
+        let mut out = vec![1.0f32; 35];
+        f32::outer_product_map_add_inplace(&mut out, &lhs32, &rhs32, &mut |v: &f32| v / 3.0, &mut Vec::new());
+        let expected = lhs32.iter().flat_map(|l| rhs32.iter().map(move |r| 1.0 + (l * r) / 3.0));
+        assert!(out.iter().zip(expected).all(|(o, e)| o.to_bits() == e.to_bits()));
+    }
+
+    #[test]
+    fn test_unmapped_outer_product_matches_scalar_loop() {
+        let lhs: Vec<f64> = (0..5).map(|i| (i as f64 * 0.7).sin()).collect();
+        let rhs: Vec<f64> = (0..7).map(|i| (i as f64 * 1.3).cos()).collect();
+
+        let mut out = vec![1.0f64; 35];
+        f64::outer_product_add_inplace(&mut out, &lhs, &rhs);
+        let expected = lhs.iter().flat_map(|l| rhs.iter().map(move |r| 1.0 + l * r));
+        assert!(out.iter().zip(expected).all(|(o, e)| o.to_bits() == e.to_bits()));
+
+        let lhs32: Vec<f32> = lhs.iter().map(|v| *v as f32).collect();
+        let rhs32: Vec<f32> = rhs.iter().map(|v| *v as f32).collect();
+        let mut out = vec![1.0f32; 35];
+        f32::outer_product_add_inplace(&mut out, &lhs32, &rhs32);
+        let expected = lhs32.iter().flat_map(|l| rhs32.iter().map(move |r| 1.0 + l * r));
+        assert!(out.iter().zip(expected).all(|(o, e)| o.to_bits() == e.to_bits()));
+    }
+}
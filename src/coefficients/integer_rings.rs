@@ -0,0 +1,169 @@
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+
+use super::{CoefficientBase, Commutative, CoefficientRing, FromDegreeType};
+use crate::DegreeType;
+
+
+// The machine integer types all have the same implementation, so we generate them with a macro.
+macro_rules! impl_integer_ring {
+    ($($t:ty),*) => {$(
+
+        impl CoefficientBase for $t {}
+
+        impl Commutative for $t {}
+
+        impl FromDegreeType for $t {
+            fn from_degree(deg: &DegreeType) -> Self {
+                *deg as Self
+            }
+        }
+
+        impl CoefficientRing for $t
+        {
+            #[inline(always)]
+            fn zero() -> Self {
+                0
+            }
+            #[inline(always)]
+            fn one() -> Self {
+                1
+            }
+            #[inline(always)]
+            fn mone() -> Self {
+                -1
+            }
+
+            #[inline(always)]
+            fn uminus(&self) -> Self {
+                -(*self)
+            }
+
+            #[inline(always)]
+            fn add(&self, other: &Self) -> Self {
+                self + other
+            }
+            #[inline(always)]
+            fn sub(&self, other: &Self) -> Self {
+                self - other
+            }
+            #[inline(always)]
+            fn mul(&self, other: &Self) -> Self {
+                self * other
+            }
+            #[inline(always)]
+            fn add_inplace(&mut self, other: &Self) -> &mut Self {
+                *self += other;
+                self
+            }
+            #[inline(always)]
+            fn sub_inplace(&mut self, other: &Self) -> &mut Self {
+                *self -= other;
+                self
+            }
+            #[inline(always)]
+            fn mul_inplace(&mut self, other: &Self) -> &mut Self {
+                *self *= other;
+                self
+            }
+            #[inline(always)]
+            fn lmul_inplace(&mut self, other: &Self) -> &mut Self {
+                *self *= other;
+                self
+            }
+        }
+    )*};
+}
+
+impl_integer_ring!(i32, i64, i128);
+
+
+impl CoefficientBase for BigInt {}
+
+impl Commutative for BigInt {}
+
+impl FromDegreeType for BigInt {
+    fn from_degree(deg: &DegreeType) -> Self {
+        BigInt::from(*deg)
+    }
+}
+
+impl CoefficientRing for BigInt
+{
+    fn zero() -> Self {
+        Zero::zero()
+    }
+
+    fn one() -> Self {
+        One::one()
+    }
+
+    fn mone() -> Self {
+        -<BigInt as One>::one()
+    }
+
+    fn uminus(&self) -> Self {
+        -self
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        self - other
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        self * other
+    }
+
+    fn add_inplace(&mut self, other: &Self) -> &mut Self {
+        *self += other;
+        self
+    }
+
+    fn sub_inplace(&mut self, other: &Self) -> &mut Self {
+        *self -= other;
+        self
+    }
+
+    fn mul_inplace(&mut self, other: &Self) -> &mut Self {
+        *self *= other;
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_machine_integer_arithmetic() {
+        let mut a = 3i64;
+
+        a.add_inplace(&4).mul_inplace(&-2);
+        assert_eq!(a, -14);
+        assert_eq!(a.uminus(), 14);
+        assert_eq!(CoefficientRing::sub(&a, &1), -15);
+    }
+
+    #[test]
+    fn test_from_degree() {
+        assert_eq!(i128::from_degree(&7), 7i128);
+        assert_eq!(BigInt::from_degree(&7), BigInt::from(7));
+    }
+
+    #[test]
+    fn test_bigint_does_not_overflow() {
+        let big = BigInt::from(i64::MAX);
+        let mut r = <BigInt as CoefficientRing>::one();
+
+        r.mul_inplace(&big).mul_inplace(&big);
+
+        assert_eq!(r, BigInt::from(i64::MAX as i128 * i64::MAX as i128));
+        assert_eq!(r.sub(&r), <BigInt as CoefficientRing>::zero());
+    }
+}
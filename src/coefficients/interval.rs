@@ -0,0 +1,261 @@
+use std::borrow::Borrow;
+use std::fmt::{self, Display, Formatter};
+
+use super::{CoefficientBase, Commutative, CoefficientRing, CoefficientField, FromDegreeType};
+use crate::DegreeType;
+
+
+/// Closed interval of real numbers with `f64` endpoints.
+///
+/// Interval arithmetic computes an interval that is guaranteed to contain every possible result
+/// of the operation applied to values from the operands. Running a computation with interval
+/// coefficients therefore produces a rigorous enclosure of the exact result, which accounts for
+/// both uncertainty in the inputs and the rounding errors made along the way.
+///
+/// Rust offers no control over the floating point rounding mode, so outward rounding is
+/// achieved by computing each endpoint with the default round-to-nearest and then moving it
+/// outwards by one unit in the last place. The enclosures are slightly wider than with directed
+/// rounding, but remain valid.
+///
+/// Intervals can be divided by `f64` values, which is what the tensor exponential and logarithm
+/// need.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    lower: f64,
+    upper: f64,
+}
+
+
+#[inline(always)]
+fn round_down(val: f64) -> f64
+{
+    val.next_down()
+}
+
+#[inline(always)]
+fn round_up(val: f64) -> f64
+{
+    val.next_up()
+}
+
+
+impl Interval {
+
+    /// Interval with the given endpoints.
+    ///
+    /// Panics if `lower > upper` or either endpoint is NaN.
+    pub fn new(lower: f64, upper: f64) -> Self
+    {
+        assert!(lower <= upper, "invalid interval [{}, {}]", lower, upper);
+        Self { lower, upper }
+    }
+
+    /// Interval containing the single value `val`.
+    pub const fn point(val: f64) -> Self
+    {
+        Self { lower: val, upper: val }
+    }
+
+    /// Interval of radius `radius` centred on `centre`, widened to account for rounding.
+    pub fn from_centre_radius(centre: f64, radius: f64) -> Self
+    {
+        Self::new(round_down(centre - radius), round_up(centre + radius))
+    }
+
+    pub fn lower(&self) -> f64
+    {
+        self.lower
+    }
+
+    pub fn upper(&self) -> f64
+    {
+        self.upper
+    }
+
+    pub fn midpoint(&self) -> f64
+    {
+        self.lower + 0.5 * (self.upper - self.lower)
+    }
+
+    /// Width of the interval, rounded upwards.
+    pub fn width(&self) -> f64
+    {
+        round_up(self.upper - self.lower)
+    }
+
+    pub fn contains(&self, val: f64) -> bool
+    {
+        self.lower <= val && val <= self.upper
+    }
+
+    /// Smallest interval containing both this interval and `other`.
+    pub fn hull(&self, other: &Self) -> Self
+    {
+        Self {
+            lower: self.lower.min(other.lower),
+            upper: self.upper.max(other.upper),
+        }
+    }
+}
+
+
+impl From<f64> for Interval {
+    fn from(val: f64) -> Self {
+        Self::point(val)
+    }
+}
+
+impl From<i8> for Interval {
+    fn from(val: i8) -> Self {
+        Self::point(f64::from(val))
+    }
+}
+
+impl From<i16> for Interval {
+    fn from(val: i16) -> Self {
+        Self::point(f64::from(val))
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.lower, self.upper)
+    }
+}
+
+
+impl CoefficientBase for Interval {}
+
+impl Commutative for Interval {}
+
+impl FromDegreeType for Interval {
+    fn from_degree(deg: &DegreeType) -> Self {
+        Self::point(*deg as f64)
+    }
+}
+
+impl CoefficientRing for Interval
+{
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::point(0.0)
+    }
+    #[inline(always)]
+    fn one() -> Self {
+        Self::point(1.0)
+    }
+    #[inline(always)]
+    fn mone() -> Self {
+        Self::point(-1.0)
+    }
+
+    #[inline(always)]
+    fn uminus(&self) -> Self {
+        Self { lower: -self.upper, upper: -self.lower }
+    }
+
+    #[inline(always)]
+    fn add(&self, other: &Self) -> Self {
+        Self {
+            lower: round_down(self.lower + other.lower),
+            upper: round_up(self.upper + other.upper),
+        }
+    }
+    #[inline(always)]
+    fn sub(&self, other: &Self) -> Self {
+        Self {
+            lower: round_down(self.lower - other.upper),
+            upper: round_up(self.upper - other.lower),
+        }
+    }
+    fn mul(&self, other: &Self) -> Self {
+        let products = [
+            self.lower * other.lower,
+            self.lower * other.upper,
+            self.upper * other.lower,
+            self.upper * other.upper,
+        ];
+        let lower = products.iter().copied().fold(f64::INFINITY, f64::min);
+        let upper = products.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        Self { lower: round_down(lower), upper: round_up(upper) }
+    }
+    #[inline(always)]
+    fn add_inplace(&mut self, other: &Self) -> &mut Self {
+        *self = CoefficientRing::add(&*self, other);
+        self
+    }
+    #[inline(always)]
+    fn sub_inplace(&mut self, other: &Self) -> &mut Self {
+        *self = CoefficientRing::sub(&*self, other);
+        self
+    }
+    #[inline(always)]
+    fn mul_inplace(&mut self, other: &Self) -> &mut Self {
+        *self = CoefficientRing::mul(&*self, other);
+        self
+    }
+}
+
+impl CoefficientField for Interval
+{
+    type RationalType = f64;
+
+    fn inv(arg: impl Borrow<Self::RationalType>) -> Self
+    {
+        let val = 1.0 / arg.borrow();
+        Self { lower: round_down(val), upper: round_up(val) }
+    }
+
+    fn div(&self, other: &Self::RationalType) -> Self {
+        let (lower, upper) = if *other < 0.0 {
+            (self.upper / other, self.lower / other)
+        } else {
+            (self.lower / other, self.upper / other)
+        };
+        Self { lower: round_down(lower), upper: round_up(upper) }
+    }
+
+    fn div_inplace(&mut self, other: &Self::RationalType) -> &mut Self {
+        *self = self.div(other);
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_add_rounds_outwards() {
+        let tenth = Interval::from(1.0).div(&10.0);
+        let mut sum = Interval::zero();
+        for _ in 0..10 {
+            sum.add_inplace(&tenth);
+        }
+
+        assert!(sum.contains(1.0));
+        assert!(sum.lower() < sum.upper());
+        assert!(sum.width() < 1e-14);
+    }
+
+    #[test]
+    fn test_mul_signs() {
+        let a = Interval::new(-2.0, 3.0);
+        let b = Interval::new(-1.0, 4.0);
+        let result = a.mul(&b);
+
+        assert!(result.contains(-8.0) && result.contains(12.0));
+        assert!(result.lower() > -8.0 - 1e-12 && result.upper() < 12.0 + 1e-12);
+    }
+
+    #[test]
+    fn test_negative_divisor_swaps_endpoints() {
+        let result = Interval::new(1.0, 2.0).div(&-4.0);
+
+        assert!(result.contains(-0.5) && result.contains(-0.25));
+        assert!(result.upper() < 0.0);
+    }
+}
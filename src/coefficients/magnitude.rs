@@ -0,0 +1,85 @@
+use num_bigint::BigInt;
+use num_traits::{Signed, ToPrimitive};
+
+use super::{CoefficientRing, Complex, DoubleDouble, Interval, Rational};
+
+
+/// Coefficients with an absolute value, from which the norms of vectors are computed.
+///
+/// As with the tolerances used by `ApproxEq`, magnitudes are always `f64`, so exact types are
+/// rounded when their magnitude is taken. Types without a natural absolute value, such as
+/// polynomials and modular integers, do not implement this trait.
+pub trait Magnitude : CoefficientRing
+{
+    fn magnitude(&self) -> f64;
+}
+
+
+macro_rules! impl_magnitude_primitive {
+    ($($t:ty),*) => {$(
+        impl Magnitude for $t {
+            #[inline(always)]
+            fn magnitude(&self) -> f64 {
+                (*self as f64).abs()
+            }
+        }
+    )*};
+}
+
+impl_magnitude_primitive!(f32, f64, i32, i64, i128);
+
+impl Magnitude for BigInt {
+    fn magnitude(&self) -> f64 {
+        self.abs().to_f64().unwrap_or(f64::INFINITY)
+    }
+}
+
+impl Magnitude for Rational {
+    fn magnitude(&self) -> f64 {
+        self.to_f64().abs()
+    }
+}
+
+impl Magnitude for DoubleDouble {
+    fn magnitude(&self) -> f64 {
+        self.abs().to_f64()
+    }
+}
+
+macro_rules! impl_magnitude_complex {
+    ($($t:ty),*) => {$(
+        impl Magnitude for Complex<$t> {
+            fn magnitude(&self) -> f64 {
+                self.norm() as f64
+            }
+        }
+    )*};
+}
+
+impl_magnitude_complex!(f32, f64);
+
+// The largest magnitude of any point in the interval, so that norms of interval vectors bound
+// the norms of the vectors they contain.
+impl Magnitude for Interval {
+    fn magnitude(&self) -> f64 {
+        self.lower().abs().max(self.upper().abs())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_magnitudes() {
+        assert_eq!((-2.5f32).magnitude(), 2.5);
+        assert_eq!((-3i64).magnitude(), 3.0);
+        // BigInt has an inherent method of the same name
+        assert_eq!(Magnitude::magnitude(&BigInt::from(-7)), 7.0);
+        assert_eq!(Rational::new(-1, 4).magnitude(), 0.25);
+        assert_eq!(Complex::new(3.0f64, -4.0).magnitude(), 5.0);
+        assert_eq!(Interval::new(-3.0, 1.0).magnitude(), 3.0);
+    }
+}
@@ -0,0 +1,218 @@
+use std::borrow::Borrow;
+
+use super::{CoefficientBase, CoefficientRing, CoefficientField, FromDegreeType};
+use crate::DegreeType;
+
+
+/// Square matrix of fixed size `N` with entries from a coefficient ring.
+///
+/// Matrices form a non-commutative ring, so tensors with matrix coefficients distinguish
+/// between left and right scalar multiplication. Such tensors represent path developments into
+/// matrix groups and the solutions of linear controlled differential equations.
+///
+/// Integers and rationals act on matrices as multiples of the identity, so matrices over a
+/// field can be divided by elements of its rational type entry by entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix<S: CoefficientRing, const N: usize>([[S; N]; N]);
+
+
+impl<S: CoefficientRing, const N: usize> Matrix<S, N> {
+
+    pub fn new(rows: [[S; N]; N]) -> Self
+    {
+        Self(rows)
+    }
+
+    /// Matrix whose entries are given by `func(row, col)`.
+    pub fn from_fn(mut func: impl FnMut(usize, usize) -> S) -> Self
+    {
+        Self(std::array::from_fn(|i| std::array::from_fn(|j| func(i, j))))
+    }
+
+    /// Multiple of the identity matrix with `val` on the diagonal.
+    pub fn scalar(val: S) -> Self
+    {
+        Self::from_fn(|i, j| if i == j { val.clone() } else { S::zero() })
+    }
+
+    pub fn identity() -> Self
+    {
+        Self::scalar(S::one())
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> &S
+    {
+        &self.0[row][col]
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> &mut S
+    {
+        &mut self.0[row][col]
+    }
+
+    pub fn rows(&self) -> &[[S; N]; N]
+    {
+        &self.0
+    }
+
+    pub fn transpose(&self) -> Self
+    {
+        Self::from_fn(|i, j| self.0[j][i].clone())
+    }
+
+    fn map(&self, mut func: impl FnMut(&S) -> S) -> Self
+    {
+        Self::from_fn(|i, j| func(&self.0[i][j]))
+    }
+
+    fn zip_map(&self, other: &Self, mut func: impl FnMut(&S, &S) -> S) -> Self
+    {
+        Self::from_fn(|i, j| func(&self.0[i][j], &other.0[i][j]))
+    }
+}
+
+
+impl<S: CoefficientRing, const N: usize> From<i8> for Matrix<S, N> {
+    fn from(val: i8) -> Self {
+        Self::scalar(S::from(val))
+    }
+}
+
+impl<S: CoefficientRing, const N: usize> From<i16> for Matrix<S, N> {
+    fn from(val: i16) -> Self {
+        Self::scalar(S::from(val))
+    }
+}
+
+
+impl<S: CoefficientRing, const N: usize> CoefficientBase for Matrix<S, N> {}
+
+impl<S: CoefficientRing, const N: usize> FromDegreeType for Matrix<S, N> {
+    fn from_degree(deg: &DegreeType) -> Self {
+        Self::scalar(S::from_degree(deg))
+    }
+}
+
+impl<S: CoefficientRing, const N: usize> CoefficientRing for Matrix<S, N>
+{
+    fn zero() -> Self {
+        Self::from_fn(|_, _| S::zero())
+    }
+
+    fn one() -> Self {
+        Self::identity()
+    }
+
+    fn mone() -> Self {
+        Self::scalar(S::mone())
+    }
+
+    fn uminus(&self) -> Self {
+        self.map(S::uminus)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self.zip_map(other, S::add)
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        self.zip_map(other, S::sub)
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        Self::from_fn(|i, j| {
+            let mut acc = S::zero();
+            for k in 0..N {
+                acc.add_inplace(&self.0[i][k].mul(&other.0[k][j]));
+            }
+            acc
+        })
+    }
+
+    fn add_inplace(&mut self, other: &Self) -> &mut Self {
+        for (lhs_row, rhs_row) in self.0.iter_mut().zip(other.0.iter()) {
+            for (lhs, rhs) in lhs_row.iter_mut().zip(rhs_row.iter()) {
+                lhs.add_inplace(rhs);
+            }
+        }
+        self
+    }
+
+    fn sub_inplace(&mut self, other: &Self) -> &mut Self {
+        for (lhs_row, rhs_row) in self.0.iter_mut().zip(other.0.iter()) {
+            for (lhs, rhs) in lhs_row.iter_mut().zip(rhs_row.iter()) {
+                lhs.sub_inplace(rhs);
+            }
+        }
+        self
+    }
+
+    fn mul_inplace(&mut self, other: &Self) -> &mut Self {
+        *self = CoefficientRing::mul(&*self, other);
+        self
+    }
+}
+
+impl<S: CoefficientField, const N: usize> CoefficientField for Matrix<S, N>
+{
+    type RationalType = S::RationalType;
+
+    fn inv(arg: impl Borrow<Self::RationalType>) -> Self
+    {
+        Self::scalar(S::inv(arg))
+    }
+
+    fn div(&self, other: &Self::RationalType) -> Self {
+        self.map(|v| v.div(other))
+    }
+
+    fn div_inplace(&mut self, other: &Self::RationalType) -> &mut Self {
+        for row in self.0.iter_mut() {
+            for v in row.iter_mut() {
+                v.div_inplace(other);
+            }
+        }
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    type M2 = Matrix<i64, 2>;
+
+    #[test]
+    fn test_multiplication_does_not_commute() {
+        let a = M2::new([[0, 1], [0, 0]]);
+        let b = M2::new([[0, 0], [1, 0]]);
+
+        assert_eq!(a.mul(&b), M2::new([[1, 0], [0, 0]]));
+        assert_eq!(b.mul(&a), M2::new([[0, 0], [0, 1]]));
+    }
+
+    #[test]
+    fn test_lmul_inplace_multiplies_on_left() {
+        let a = M2::new([[1, 2], [3, 4]]);
+        let b = M2::new([[0, 1], [1, 0]]);
+
+        let mut result = a.clone();
+        result.lmul_inplace(&b);
+        assert_eq!(result, b.mul(&a));
+
+        result = a.clone();
+        result.mul_inplace(&b);
+        assert_eq!(result, a.mul(&b));
+    }
+
+    #[test]
+    fn test_integers_act_as_scalars() {
+        let a = M2::new([[1, 2], [3, 4]]);
+
+        assert_eq!(a.mul(&M2::from(3i8)), M2::new([[3, 6], [9, 12]]));
+        assert_eq!(M2::from_degree(&2), M2::new([[2, 0], [0, 2]]));
+        assert_eq!(Matrix::<f64, 2>::from(4i8).div(&8.0), Matrix::scalar(0.5));
+    }
+}
@@ -11,6 +11,10 @@ pub trait CoefficientBase
 {}
 
 
+/// Marker for coefficient types whose multiplication is commutative.
+///
+/// Nothing in the library requires commutativity, but types that are commutative should
+/// implement this so that generic code can rely on it when it matters.
 pub trait Commutative
 {}
 
@@ -20,34 +24,186 @@ pub trait FromDegreeType {
 
 }
 
-pub trait CoefficientField
-    : CoefficientBase + Commutative
+/// Scalars that can be added, subtracted, and multiplied.
+///
+/// This is the minimal set of operations needed to form vectors and to multiply elements of an
+/// algebra. Integer types are rings but not fields, so they implement this trait but not
+/// `CoefficientField`.
+///
+/// Multiplication need not be commutative, so the order of the operands matters: `mul` and
+/// `mul_inplace` compute `self * other`, whereas `lmul_inplace` computes `other * self`.
+pub trait CoefficientRing
+    : CoefficientBase
     + From<i8> + From<i16> + FromDegreeType
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn mone() -> Self;
+
+    fn uminus(&self) -> Self;
+
+    fn add(&self, other: &Self) -> Self;
+    fn sub(&self, other: &Self) -> Self;
+    fn mul(&self, other: &Self) -> Self;
+
+    fn add_inplace(&mut self, other: &Self) -> &mut Self;
+    fn sub_inplace(&mut self, other: &Self) -> &mut Self;
+    fn mul_inplace(&mut self, other: &Self) -> &mut Self;
+
+    fn lmul_inplace(&mut self, other: &Self) -> &mut Self
+    {
+        *self = other.mul(self);
+        self
+    }
+
+    /// Add each element of `rhs` to the corresponding element of `out`, over the length of the
+    /// shorter slice.
+    ///
+    /// This and the following slice methods are the kernels of dense vector and tensor
+    /// arithmetic. The defaults work one element at a time; types that can do better, such as
+    /// `f32` and `f64` with SIMD instructions, override them.
+    fn add_slice_inplace(out: &mut [Self], rhs: &[Self])
+    {
+        for (o, r) in out.iter_mut().zip(rhs) {
+            o.add_inplace(r);
+        }
+    }
+
+    /// Subtract each element of `rhs` from the corresponding element of `out`, over the length
+    /// of the shorter slice.
+    fn sub_slice_inplace(out: &mut [Self], rhs: &[Self])
+    {
+        for (o, r) in out.iter_mut().zip(rhs) {
+            o.sub_inplace(r);
+        }
+    }
+
+    /// Multiply each element of `out` on the right by `scalar`.
+    fn mul_slice_inplace(out: &mut [Self], scalar: &Self)
+    {
+        for o in out.iter_mut() {
+            o.mul_inplace(scalar);
+        }
+    }
+
+    /// Multiply each element of `out` on the left by `scalar`.
+    fn lmul_slice_inplace(out: &mut [Self], scalar: &Self)
+    {
+        for o in out.iter_mut() {
+            o.lmul_inplace(scalar);
+        }
+    }
+
+    /// Add `rhs[i] * scalar` to each element of `out`, over the length of the shorter slice.
+    fn add_mul_slice_inplace(out: &mut [Self], rhs: &[Self], scalar: &Self)
+    {
+        for (o, r) in out.iter_mut().zip(rhs) {
+            o.add_inplace(&r.mul(scalar));
+        }
+    }
+
+    /// Add `scalar * rhs[i]` to each element of `out`, over the length of the shorter slice.
+    fn add_lmul_slice_inplace(out: &mut [Self], rhs: &[Self], scalar: &Self)
+    {
+        for (o, r) in out.iter_mut().zip(rhs) {
+            o.add_inplace(&scalar.mul(r));
+        }
+    }
+
+    /// Add `lhs[i] * rhs[j]` to `out[i * rhs.len() + j]` for every `i` and `j`.
+    fn outer_product_add_inplace(out: &mut [Self], lhs: &[Self], rhs: &[Self])
+    {
+        let mut out_iter = out.iter_mut();
+        for lhs_v in lhs {
+            for rhs_v in rhs {
+                out_iter.next().unwrap().add_inplace(&lhs_v.mul(rhs_v));
+            }
+        }
+    }
+
+    /// Add `func(lhs[i] * rhs[j])` to `out[i * rhs.len() + j]` for every `i` and `j`.
+    ///
+    /// The function `func` must be linear, as are all those used by `Algebra`. Overrides must
+    /// still apply it to each product, since for floating point types applying it to one of
+    /// the factors instead changes the rounding of the result. Overrides may form the products
+    /// in the buffer, which the caller keeps between calls to save allocating it each time.
+    fn outer_product_map_add_inplace<F>(
+        out: &mut [Self],
+        lhs: &[Self],
+        rhs: &[Self],
+        func: &mut F,
+        _buffer: &mut Vec<Self>,
+    )
+        where F: FnMut(&Self) -> Self
+    {
+        let mut out_iter = out.iter_mut();
+        for lhs_v in lhs {
+            for rhs_v in rhs {
+                out_iter.next().unwrap().add_inplace(&func(&lhs_v.mul(rhs_v)));
+            }
+        }
+    }
+
+    /// Replace each element `v` of `out` by `func(v * scalar)`.
+    ///
+    /// As for `outer_product_map_add_inplace`, `func` must be linear and is applied to each
+    /// product.
+    fn mul_map_slice_inplace<F>(out: &mut [Self], scalar: &Self, func: &mut F)
+        where F: FnMut(&Self) -> Self
+    {
+        for o in out.iter_mut() {
+            *o = func(&o.mul(scalar));
+        }
+    }
+
+}
+
+
+/// Rings that also support division by elements of the associated rational type.
+///
+/// Division is only needed for operations such as the tensor exponential and logarithm, where
+/// coefficients are divided by integers. Elements of the rational type must commute with every
+/// element of the ring, so there is no distinction between left and right division.
+pub trait CoefficientField : CoefficientRing
 {
     type RationalType: From<i8> + From<i16> + FromDegreeType;
-    const ZERO: Self;
-    const ONE: Self;
-    const MONE: Self;
 
     fn from_rational<I: Into<Self>>(num: I, denom: impl Into<Self::RationalType>) -> Self
     {
         num.into().div(&denom.into())
     }
 
-    fn uminus(&self) -> Self;
     fn inv(arg: impl Borrow<Self::RationalType>) -> Self;
 
-    fn add(&self, other: &Self) -> Self;
-    fn sub(&self, other: &Self) -> Self;
-    fn mul(&self, other: &Self) -> Self;
     fn div(&self, other: &Self::RationalType) -> Self;
 
-    fn add_inplace(&mut self, other: &Self) -> &mut Self;
-    fn sub_inplace(&mut self, other: &Self) -> &mut Self;
-    fn mul_inplace(&mut self, other: &Self) -> &mut Self;
     fn div_inplace(&mut self, other: &Self::RationalType) -> &mut Self;
 
 }
 
+mod approx;
+mod complex;
+mod conversion;
+mod double_double;
+mod dual;
+mod floating_point_fields;
+mod integer_rings;
+mod interval;
+mod magnitude;
+mod matrix;
+mod modular;
+mod polynomial;
+mod rational;
+mod simd;
 
-mod floating_point_fields;
\ No newline at end of file
+pub use approx::{ApproxEq, ZeroPolicy};
+pub use complex::Complex;
+pub use conversion::ToCoefficient;
+pub use double_double::DoubleDouble;
+pub use dual::Dual;
+pub use interval::Interval;
+pub use magnitude::Magnitude;
+pub use matrix::Matrix;
+pub use modular::ModP;
+pub use polynomial::{Monomial, Polynomial};
+pub use rational::Rational;
\ No newline at end of file
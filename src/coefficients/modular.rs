@@ -0,0 +1,225 @@
+use std::borrow::Borrow;
+use std::fmt::{self, Display, Formatter};
+
+use super::{CoefficientBase, Commutative, CoefficientRing, CoefficientField, FromDegreeType};
+use crate::DegreeType;
+
+
+/// Integers modulo `P`.
+///
+/// When `P` is prime this is the finite field with `P` elements, and so a `CoefficientField`
+/// whose rational type is itself. Computing modulo a large prime verifies combinatorial
+/// identities without the rounding errors of floating point or the cost of big integers.
+/// Division by an element that is not invertible modulo `P`, which includes zero, panics.
+///
+/// Values are always stored reduced into the range `0..P`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ModP<const P: u64>(u64);
+
+
+impl<const P: u64> ModP<P> {
+
+    pub const fn new(val: u64) -> Self
+    {
+        Self(val % P)
+    }
+
+    pub fn from_signed(val: i64) -> Self
+    {
+        Self((val as i128).rem_euclid(P as i128) as u64)
+    }
+
+    /// Representative of this residue class in the range `0..P`.
+    pub const fn value(&self) -> u64
+    {
+        self.0
+    }
+
+    pub fn pow(&self, mut exp: u64) -> Self
+    {
+        let mut base = *self;
+        let mut result = Self::new(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result.mul_inplace(&base);
+            }
+            base = CoefficientRing::mul(&base, &base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Multiplicative inverse, or `None` if this element is not invertible modulo `P`.
+    pub fn checked_inv(&self) -> Option<Self>
+    {
+        // Extended Euclidean algorithm, tracking only the coefficient of self
+        let (mut r0, mut r1) = (P as i128, self.0 as i128);
+        let (mut t0, mut t1) = (0i128, 1i128);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        if r0 == 1 {
+            Some(Self(t0.rem_euclid(P as i128) as u64))
+        } else {
+            None
+        }
+    }
+}
+
+
+impl<const P: u64> From<i8> for ModP<P> {
+    fn from(val: i8) -> Self {
+        Self::from_signed(val as i64)
+    }
+}
+
+impl<const P: u64> From<i16> for ModP<P> {
+    fn from(val: i16) -> Self {
+        Self::from_signed(val as i64)
+    }
+}
+
+impl<const P: u64> From<i64> for ModP<P> {
+    fn from(val: i64) -> Self {
+        Self::from_signed(val)
+    }
+}
+
+impl<const P: u64> From<u64> for ModP<P> {
+    fn from(val: u64) -> Self {
+        Self::new(val)
+    }
+}
+
+impl<const P: u64> Display for ModP<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+
+impl<const P: u64> CoefficientBase for ModP<P> {}
+
+impl<const P: u64> Commutative for ModP<P> {}
+
+impl<const P: u64> FromDegreeType for ModP<P> {
+    fn from_degree(deg: &DegreeType) -> Self {
+        Self::new(*deg as u64)
+    }
+}
+
+impl<const P: u64> CoefficientRing for ModP<P>
+{
+    #[inline(always)]
+    fn zero() -> Self {
+        Self(0)
+    }
+    #[inline(always)]
+    fn one() -> Self {
+        Self::new(1)
+    }
+    #[inline(always)]
+    fn mone() -> Self {
+        Self::new(P - 1)
+    }
+
+    #[inline(always)]
+    fn uminus(&self) -> Self {
+        Self::new(P - self.0)
+    }
+
+    #[inline(always)]
+    fn add(&self, other: &Self) -> Self {
+        Self(((self.0 as u128 + other.0 as u128) % P as u128) as u64)
+    }
+    #[inline(always)]
+    fn sub(&self, other: &Self) -> Self {
+        Self(((self.0 as u128 + (P - other.0) as u128) % P as u128) as u64)
+    }
+    #[inline(always)]
+    fn mul(&self, other: &Self) -> Self {
+        Self(((self.0 as u128 * other.0 as u128) % P as u128) as u64)
+    }
+    #[inline(always)]
+    fn add_inplace(&mut self, other: &Self) -> &mut Self {
+        *self = CoefficientRing::add(&*self, other);
+        self
+    }
+    #[inline(always)]
+    fn sub_inplace(&mut self, other: &Self) -> &mut Self {
+        *self = CoefficientRing::sub(&*self, other);
+        self
+    }
+    #[inline(always)]
+    fn mul_inplace(&mut self, other: &Self) -> &mut Self {
+        *self = CoefficientRing::mul(&*self, other);
+        self
+    }
+}
+
+impl<const P: u64> CoefficientField for ModP<P>
+{
+    type RationalType = ModP<P>;
+
+    fn inv(arg: impl Borrow<Self::RationalType>) -> Self
+    {
+        let arg = arg.borrow();
+        arg.checked_inv()
+            .unwrap_or_else(|| panic!("{} is not invertible modulo {}", arg, P))
+    }
+
+    fn div(&self, other: &Self::RationalType) -> Self {
+        CoefficientRing::mul(self, &Self::inv(other))
+    }
+
+    fn div_inplace(&mut self, other: &Self::RationalType) -> &mut Self {
+        self.mul_inplace(&Self::inv(other))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    type F7 = ModP<7>;
+    type BigPrime = ModP<18446744073709551557>;
+
+    #[test]
+    fn test_reduction() {
+        assert_eq!(F7::from(-1i8), F7::new(6));
+        assert_eq!(F7::from(100i16), F7::new(2));
+        assert_eq!(F7::from_degree(&9), F7::new(2));
+        assert_eq!(F7::new(3).uminus(), F7::new(4));
+        assert_eq!(F7::zero().uminus(), F7::zero());
+    }
+
+    #[test]
+    fn test_inverse() {
+        for v in 1..7 {
+            let x = F7::new(v);
+            assert_eq!(CoefficientRing::mul(&x, &F7::inv(x)), F7::one());
+        }
+        assert_eq!(F7::new(3).div(&F7::new(5)), F7::new(2));
+        assert_eq!(ModP::<8>::new(4).checked_inv(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_divide_by_zero_panics() {
+        F7::one().div(&F7::zero());
+    }
+
+    #[test]
+    fn test_large_modulus_does_not_overflow() {
+        let x = BigPrime::mone();
+
+        assert_eq!(CoefficientRing::mul(&x, &x), BigPrime::one());
+        assert_eq!(CoefficientRing::add(&x, &x), BigPrime::from(-2i8));
+        // Fermat's little theorem
+        assert_eq!(BigPrime::new(12345).pow(18446744073709551556), BigPrime::one());
+    }
+}
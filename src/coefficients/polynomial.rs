@@ -0,0 +1,409 @@
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+use super::{CoefficientBase, Commutative, CoefficientRing, CoefficientField, FromDegreeType};
+use crate::DegreeType;
+
+
+/// Product of named indeterminates raised to positive powers.
+///
+/// The indeterminates are stored in a sorted map so that two monomials are equal precisely when
+/// they consist of the same indeterminates with the same powers. The empty monomial is the unit.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Monomial(BTreeMap<String, DegreeType>);
+
+
+impl Monomial {
+
+    pub fn new() -> Self
+    {
+        Self(BTreeMap::new())
+    }
+
+    pub fn from_indeterminate(name: impl Into<String>) -> Self
+    {
+        Self::from_power(name, 1)
+    }
+
+    pub fn from_power(name: impl Into<String>, power: DegreeType) -> Self
+    {
+        let mut inner = BTreeMap::new();
+        if power > 0 {
+            inner.insert(name.into(), power);
+        }
+        Self(inner)
+    }
+
+    /// Total degree of the monomial.
+    pub fn degree(&self) -> DegreeType
+    {
+        self.0.values().sum()
+    }
+
+    /// Power of the named indeterminate in this monomial, which is zero if it does not appear.
+    pub fn power(&self, name: &str) -> DegreeType
+    {
+        self.0.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn is_unit(&self) -> bool
+    {
+        self.0.is_empty()
+    }
+
+    pub fn mul(&self, other: &Self) -> Self
+    {
+        let mut result = self.clone();
+        for (name, power) in other.0.iter() {
+            *result.0.entry(name.clone()).or_insert(0) += *power;
+        }
+        result
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(&str, DegreeType)>
+    {
+        self.0.iter().map(|(name, power)| (name.as_str(), *power))
+    }
+}
+
+
+impl Display for Monomial {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, (name, power)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            if *power == 1 {
+                write!(f, "{}", name)?;
+            } else {
+                write!(f, "{}^{}", name, power)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+/// Commutative polynomial in named indeterminates with coefficients from a field.
+///
+/// Polynomials can be used as the coefficients of vectors and tensors to perform symbolic
+/// computations. For instance, the exponential of the tensor `a*e1 + b*e2`, where `a` and `b` are
+/// indeterminates, gives the signature of a general linear path in closed form.
+///
+/// Polynomials only form a ring, but they can be divided by elements of the rational type of
+/// the underlying field. This is all that is needed for the tensor exponential and logarithm, so
+/// polynomials implement `CoefficientField` with the same rational type as their coefficients.
+///
+/// Terms with zero coefficient are never stored, so the derived equality is equality of
+/// polynomials.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial<S: CoefficientField>(BTreeMap<Monomial, S>);
+
+
+impl<S: CoefficientField> Polynomial<S> {
+
+    pub fn new() -> Self
+    {
+        Self(BTreeMap::new())
+    }
+
+    /// Polynomial consisting of a single indeterminate with unit coefficient.
+    pub fn indeterminate(name: impl Into<String>) -> Self
+    {
+        Self::from_term(Monomial::from_indeterminate(name), S::one())
+    }
+
+    pub fn constant(val: S) -> Self
+    {
+        Self::from_term(Monomial::new(), val)
+    }
+
+    pub fn from_term(monomial: Monomial, coeff: S) -> Self
+    {
+        let mut inner = BTreeMap::new();
+        if coeff != S::zero() {
+            inner.insert(monomial, coeff);
+        }
+        Self(inner)
+    }
+
+    pub fn from_terms(terms: impl IntoIterator<Item=(Monomial, S)>) -> Self
+    {
+        let mut result = Self::new();
+        for (monomial, coeff) in terms {
+            result.add_term(monomial, &coeff);
+        }
+        result
+    }
+
+    /// Coefficient of the given monomial, which is zero if it does not appear.
+    pub fn coefficient(&self, monomial: &Monomial) -> S
+    {
+        self.0.get(monomial).cloned().unwrap_or_else(S::zero)
+    }
+
+    /// Maximum total degree of the terms, or zero for constant polynomials.
+    pub fn degree(&self) -> DegreeType
+    {
+        self.0.keys().map(Monomial::degree).max().unwrap_or(0)
+    }
+
+    pub fn is_zero(&self) -> bool
+    {
+        self.0.is_empty()
+    }
+
+    pub fn num_terms(&self) -> usize
+    {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(&Monomial, &S)>
+    {
+        self.0.iter()
+    }
+
+    /// Evaluate the polynomial by substituting a value for each indeterminate.
+    pub fn evaluate(&self, mut values: impl FnMut(&str) -> S) -> S
+    {
+        let mut result = S::zero();
+        for (monomial, coeff) in self.0.iter() {
+            let mut term = coeff.clone();
+            for (name, power) in monomial.iter() {
+                let val = values(name);
+                for _ in 0..power {
+                    term.mul_inplace(&val);
+                }
+            }
+            result.add_inplace(&term);
+        }
+        result
+    }
+
+    fn add_term(&mut self, monomial: Monomial, coeff: &S)
+    {
+        let entry = self.0.entry(monomial.clone()).or_insert_with(S::zero);
+        entry.add_inplace(coeff);
+        if *entry == S::zero() {
+            self.0.remove(&monomial);
+        }
+    }
+
+    fn map_coefficients(&mut self, mut func: impl FnMut(&mut S))
+    {
+        self.0.values_mut().for_each(&mut func);
+        self.0.retain(|_, v| *v != S::zero());
+    }
+}
+
+
+impl<S: CoefficientField> Default for Polynomial<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: CoefficientField> From<i8> for Polynomial<S> {
+    fn from(val: i8) -> Self {
+        Self::constant(S::from(val))
+    }
+}
+
+impl<S: CoefficientField> From<i16> for Polynomial<S> {
+    fn from(val: i16) -> Self {
+        Self::constant(S::from(val))
+    }
+}
+
+impl<S: CoefficientField> From<Monomial> for Polynomial<S> {
+    fn from(monomial: Monomial) -> Self {
+        Self::from_term(monomial, S::one())
+    }
+}
+
+impl<S: CoefficientField + Display> Display for Polynomial<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "{}", S::zero());
+        }
+
+        for (i, (monomial, coeff)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " + ")?;
+            }
+            if monomial.is_unit() {
+                write!(f, "{}", coeff)?;
+            } else if *coeff == S::one() {
+                write!(f, "{}", monomial)?;
+            } else {
+                write!(f, "{} {}", coeff, monomial)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+impl<S: CoefficientField> CoefficientBase for Polynomial<S> {}
+
+impl<S: CoefficientField + Commutative> Commutative for Polynomial<S> {}
+
+impl<S: CoefficientField> FromDegreeType for Polynomial<S> {
+    fn from_degree(deg: &DegreeType) -> Self {
+        Self::constant(S::from_degree(deg))
+    }
+}
+
+impl<S: CoefficientField> CoefficientRing for Polynomial<S>
+{
+    fn zero() -> Self {
+        Self::new()
+    }
+
+    fn one() -> Self {
+        Self::constant(S::one())
+    }
+
+    fn mone() -> Self {
+        Self::constant(S::mone())
+    }
+
+    fn uminus(&self) -> Self {
+        Self(self.0.iter().map(|(k, v)| (k.clone(), v.uminus())).collect())
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.add_inplace(other);
+        result
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.sub_inplace(other);
+        result
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        for (lhs_m, lhs_c) in self.0.iter() {
+            for (rhs_m, rhs_c) in other.0.iter() {
+                result.add_term(lhs_m.mul(rhs_m), &lhs_c.mul(rhs_c));
+            }
+        }
+        result
+    }
+
+    fn add_inplace(&mut self, other: &Self) -> &mut Self {
+        for (monomial, coeff) in other.0.iter() {
+            self.add_term(monomial.clone(), coeff);
+        }
+        self
+    }
+
+    fn sub_inplace(&mut self, other: &Self) -> &mut Self {
+        for (monomial, coeff) in other.0.iter() {
+            self.add_term(monomial.clone(), &coeff.uminus());
+        }
+        self
+    }
+
+    fn mul_inplace(&mut self, other: &Self) -> &mut Self {
+        *self = CoefficientRing::mul(&*self, other);
+        self
+    }
+}
+
+impl<S: CoefficientField> CoefficientField for Polynomial<S>
+{
+    type RationalType = S::RationalType;
+
+    fn inv(arg: impl Borrow<Self::RationalType>) -> Self
+    {
+        Self::constant(S::inv(arg))
+    }
+
+    fn div(&self, other: &Self::RationalType) -> Self {
+        let mut result = self.clone();
+        result.div_inplace(other);
+        result
+    }
+
+    fn div_inplace(&mut self, other: &Self::RationalType) -> &mut Self {
+        self.map_coefficients(|v| { v.div_inplace(other); });
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::coefficients::Rational;
+
+    type Poly = Polynomial<Rational>;
+
+    fn x() -> Poly {
+        Poly::indeterminate("x")
+    }
+
+    fn y() -> Poly {
+        Poly::indeterminate("y")
+    }
+
+    #[test]
+    fn test_add_cancels_terms() {
+        let p = x().add(&y());
+        let result = p.sub(&y());
+
+        assert_eq!(result, x());
+        assert_eq!(result.num_terms(), 1);
+        assert!(x().sub(&x()).is_zero());
+    }
+
+    #[test]
+    fn test_multiply_binomial() {
+        let p = x().add(&y());
+        let sq = p.mul(&p);
+
+        let expected = Poly::from_terms(vec![
+            (Monomial::from_power("x", 2), Rational::from(1i8)),
+            (Monomial::from_indeterminate("x").mul(&Monomial::from_indeterminate("y")), Rational::from(2i8)),
+            (Monomial::from_power("y", 2), Rational::from(1i8)),
+        ]);
+
+        assert_eq!(sq, expected);
+        assert_eq!(sq.degree(), 2);
+    }
+
+    #[test]
+    fn test_divide_by_rational() {
+        let p = x().add(&Poly::one());
+        let result = p.div(&Rational::from(2i8));
+
+        assert_eq!(result.coefficient(&Monomial::from_indeterminate("x")), Rational::new(1, 2));
+        assert_eq!(result.coefficient(&Monomial::new()), Rational::new(1, 2));
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let p = x().mul(&x()).add(&y().mul(&Poly::from(3i8)));
+
+        let val = p.evaluate(|name| match name {
+            "x" => Rational::from(2i8),
+            _ => Rational::new(1, 3),
+        });
+
+        assert_eq!(val, Rational::from(5i8));
+    }
+
+    #[test]
+    fn test_display() {
+        let p = x().mul(&x()).mul(&y()).div(&Rational::from(2i8)).add(&Poly::one());
+
+        assert_eq!(format!("{}", p), "1 + 1/2 x^2 y");
+        assert_eq!(format!("{}", Poly::zero()), "0");
+    }
+}
@@ -0,0 +1,258 @@
+use std::borrow::Borrow;
+use std::fmt::{self, Display, Formatter};
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, ToPrimitive, Zero};
+
+use super::{CoefficientBase, Commutative, CoefficientRing, CoefficientField, FromDegreeType};
+use crate::DegreeType;
+
+
+/// Arbitrary precision rational number.
+///
+/// Arithmetic with rationals is exact, so computations such as the tensor exponential and
+/// logarithm do not accumulate rounding errors. This makes the type ideal for testing, and for
+/// any computation where exact equality of results is needed. The cost is that the numerator and
+/// denominator can grow without bound, so rational arithmetic is considerably slower than
+/// floating point arithmetic.
+///
+/// Rationals are always stored in lowest terms with a positive denominator, so two rationals
+/// compare equal precisely when they represent the same number.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rational(BigRational);
+
+
+impl Rational {
+
+    /// Create a new rational number `numer / denom`, reduced to lowest terms.
+    ///
+    /// Panics if the denominator is zero.
+    pub fn new(numer: impl Into<BigInt>, denom: impl Into<BigInt>) -> Self
+    {
+        Self(BigRational::new(numer.into(), denom.into()))
+    }
+
+    pub fn from_integer(val: impl Into<BigInt>) -> Self
+    {
+        Self(BigRational::from_integer(val.into()))
+    }
+
+    /// Exact rational value of a finite float, or `None` for infinities and NaN.
+    pub fn from_float(val: f64) -> Option<Self>
+    {
+        BigRational::from_float(val).map(Self)
+    }
+
+    pub fn numer(&self) -> &BigInt
+    {
+        self.0.numer()
+    }
+
+    pub fn denom(&self) -> &BigInt
+    {
+        self.0.denom()
+    }
+
+    pub fn is_integer(&self) -> bool
+    {
+        self.0.is_integer()
+    }
+
+    /// Nearest floating point approximation to this rational.
+    pub fn to_f64(&self) -> f64
+    {
+        self.0.to_f64().unwrap_or(f64::NAN)
+    }
+
+    pub fn into_inner(self) -> BigRational
+    {
+        self.0
+    }
+}
+
+
+impl From<BigRational> for Rational {
+    fn from(val: BigRational) -> Self {
+        Self(val)
+    }
+}
+
+impl From<BigInt> for Rational {
+    fn from(val: BigInt) -> Self {
+        Self(BigRational::from_integer(val))
+    }
+}
+
+impl From<i8> for Rational {
+    fn from(val: i8) -> Self {
+        Self::from_integer(val)
+    }
+}
+
+impl From<i16> for Rational {
+    fn from(val: i16) -> Self {
+        Self::from_integer(val)
+    }
+}
+
+impl From<i32> for Rational {
+    fn from(val: i32) -> Self {
+        Self::from_integer(val)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(val: i64) -> Self {
+        Self::from_integer(val)
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+
+impl CoefficientBase for Rational {}
+
+impl Commutative for Rational {}
+
+impl FromDegreeType for Rational {
+    fn from_degree(deg: &DegreeType) -> Self {
+        Self::from_integer(*deg)
+    }
+}
+
+impl CoefficientRing for Rational
+{
+    fn zero() -> Self {
+        Self(BigRational::zero())
+    }
+
+    fn one() -> Self {
+        Self(BigRational::one())
+    }
+
+    fn mone() -> Self {
+        Self(-BigRational::one())
+    }
+
+    fn uminus(&self) -> Self {
+        Self(-&self.0)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Self(&self.0 + &other.0)
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        Self(&self.0 - &other.0)
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        Self(&self.0 * &other.0)
+    }
+
+    fn add_inplace(&mut self, other: &Self) -> &mut Self {
+        self.0 += &other.0;
+        self
+    }
+
+    fn sub_inplace(&mut self, other: &Self) -> &mut Self {
+        self.0 -= &other.0;
+        self
+    }
+
+    fn mul_inplace(&mut self, other: &Self) -> &mut Self {
+        self.0 *= &other.0;
+        self
+    }
+}
+
+impl CoefficientField for Rational
+{
+    type RationalType = Rational;
+
+    fn inv(arg: impl Borrow<Self::RationalType>) -> Self
+    {
+        Self(arg.borrow().0.recip())
+    }
+
+    fn div(&self, other: &Self::RationalType) -> Self {
+        Self(&self.0 / &other.0)
+    }
+
+    fn div_inplace(&mut self, other: &Self::RationalType) -> &mut Self {
+        self.0 /= &other.0;
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_new_reduces_to_lowest_terms() {
+        let r = Rational::new(6, -4);
+
+        assert_eq!(*r.numer(), BigInt::from(-3));
+        assert_eq!(*r.denom(), BigInt::from(2));
+    }
+
+    #[test]
+    fn test_from_rational_exact() {
+        let third = Rational::from_rational(1i8, 3i8);
+        let sum = third.add(&third).add(&third);
+
+        assert_eq!(sum, Rational::one());
+    }
+
+    #[test]
+    fn test_inv_and_div() {
+        let r = Rational::new(2, 7);
+
+        assert_eq!(Rational::inv(&r), Rational::new(7, 2));
+        assert_eq!(Rational::one().div(&r), Rational::new(7, 2));
+    }
+
+    #[test]
+    fn test_inplace_arithmetic() {
+        let mut r = Rational::from(1i8);
+
+        r.add_inplace(&Rational::new(1, 2));
+        assert_eq!(r, Rational::new(3, 2));
+
+        r.mul_inplace(&Rational::new(2, 3));
+        assert_eq!(r, Rational::one());
+
+        r.sub_inplace(&Rational::new(1, 3));
+        r.div_inplace(&Rational::new(-2, 3));
+        assert_eq!(r, Rational::mone());
+    }
+
+    #[test]
+    fn test_huge_values_do_not_overflow() {
+        let mut r = Rational::one();
+        let big = Rational::from(i64::MAX);
+
+        for _ in 0..4 {
+            r.mul_inplace(&big);
+        }
+        for _ in 0..4 {
+            r.div_inplace(&big);
+        }
+
+        assert_eq!(r, Rational::one());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Rational::new(-1, 3)), "-1/3");
+        assert_eq!(format!("{}", Rational::from(5i8)), "5");
+    }
+}
@@ -0,0 +1,208 @@
+//! Explicit SIMD kernels for slices of `f32` and `f64`.
+//!
+//! Each kernel checks at runtime whether the CPU supports AVX and otherwise falls back to a
+//! scalar loop, which is also used on other architectures. The kernels only use separate
+//! multiplications and additions, never fused multiply-add, so the results are the same on
+//! every path.
+
+
+macro_rules! impl_simd_kernels {
+    ($name:ident, $t:ty, $lanes:expr, $load:ident, $store:ident, $set1:ident, $add:ident, $sub:ident, $mul:ident) => {
+        pub(super) mod $name {
+            const LANES: usize = $lanes;
+
+            /// `out[i] += rhs[i]`
+            pub(in crate::coefficients) fn add_assign(out: &mut [$t], rhs: &[$t])
+            {
+                let n = out.len().min(rhs.len());
+                let (out, rhs) = (&mut out[..n], &rhs[..n]);
+
+                #[cfg(target_arch = "x86_64")]
+                {
+                    if n >= LANES && is_x86_feature_detected!("avx") {
+                        // SAFETY: AVX is available.
+                        unsafe { avx::add_assign(out, rhs) };
+                        return;
+                    }
+                }
+
+                for (o, r) in out.iter_mut().zip(rhs) {
+                    *o += *r;
+                }
+            }
+
+            /// `out[i] -= rhs[i]`
+            pub(in crate::coefficients) fn sub_assign(out: &mut [$t], rhs: &[$t])
+            {
+                let n = out.len().min(rhs.len());
+                let (out, rhs) = (&mut out[..n], &rhs[..n]);
+
+                #[cfg(target_arch = "x86_64")]
+                {
+                    if n >= LANES && is_x86_feature_detected!("avx") {
+                        // SAFETY: AVX is available.
+                        unsafe { avx::sub_assign(out, rhs) };
+                        return;
+                    }
+                }
+
+                for (o, r) in out.iter_mut().zip(rhs) {
+                    *o -= *r;
+                }
+            }
+
+            /// `out[i] *= scalar`
+            pub(in crate::coefficients) fn scale(out: &mut [$t], scalar: $t)
+            {
+                #[cfg(target_arch = "x86_64")]
+                {
+                    if out.len() >= LANES && is_x86_feature_detected!("avx") {
+                        // SAFETY: AVX is available.
+                        unsafe { avx::scale(out, scalar) };
+                        return;
+                    }
+                }
+
+                for o in out.iter_mut() {
+                    *o *= scalar;
+                }
+            }
+
+            /// `out[i] += a * x[i]`
+            pub(in crate::coefficients) fn axpy(out: &mut [$t], a: $t, x: &[$t])
+            {
+                let n = out.len().min(x.len());
+                let (out, x) = (&mut out[..n], &x[..n]);
+
+                #[cfg(target_arch = "x86_64")]
+                {
+                    if n >= LANES && is_x86_feature_detected!("avx") {
+                        // SAFETY: AVX is available.
+                        unsafe { avx::axpy(out, a, x) };
+                        return;
+                    }
+                }
+
+                for (o, v) in out.iter_mut().zip(x) {
+                    *o += a * *v;
+                }
+            }
+
+            // The slices passed to these functions have the same length.
+            #[cfg(target_arch = "x86_64")]
+            mod avx {
+                use std::arch::x86_64::*;
+
+                use super::LANES;
+
+                #[target_feature(enable = "avx")]
+                pub(super) unsafe fn add_assign(out: &mut [$t], rhs: &[$t])
+                {
+                    let done = out.len() - out.len() % LANES;
+                    for i in (0..done).step_by(LANES) {
+                        let o = out.as_mut_ptr().add(i);
+                        $store(o, $add($load(o), $load(rhs.as_ptr().add(i))));
+                    }
+                    for (o, r) in out[done..].iter_mut().zip(&rhs[done..]) {
+                        *o += *r;
+                    }
+                }
+
+                #[target_feature(enable = "avx")]
+                pub(super) unsafe fn sub_assign(out: &mut [$t], rhs: &[$t])
+                {
+                    let done = out.len() - out.len() % LANES;
+                    for i in (0..done).step_by(LANES) {
+                        let o = out.as_mut_ptr().add(i);
+                        $store(o, $sub($load(o), $load(rhs.as_ptr().add(i))));
+                    }
+                    for (o, r) in out[done..].iter_mut().zip(&rhs[done..]) {
+                        *o -= *r;
+                    }
+                }
+
+                #[target_feature(enable = "avx")]
+                pub(super) unsafe fn scale(out: &mut [$t], scalar: $t)
+                {
+                    let s = $set1(scalar);
+                    let done = out.len() - out.len() % LANES;
+                    for i in (0..done).step_by(LANES) {
+                        let o = out.as_mut_ptr().add(i);
+                        $store(o, $mul($load(o), s));
+                    }
+                    for o in out[done..].iter_mut() {
+                        *o *= scalar;
+                    }
+                }
+
+                #[target_feature(enable = "avx")]
+                pub(super) unsafe fn axpy(out: &mut [$t], a: $t, x: &[$t])
+                {
+                    let av = $set1(a);
+                    let done = out.len() - out.len() % LANES;
+                    for i in (0..done).step_by(LANES) {
+                        let o = out.as_mut_ptr().add(i);
+                        $store(o, $add($load(o), $mul(av, $load(x.as_ptr().add(i)))));
+                    }
+                    for (o, v) in out[done..].iter_mut().zip(&x[done..]) {
+                        *o += a * *v;
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_simd_kernels!(f32_kernels, f32, 8, _mm256_loadu_ps, _mm256_storeu_ps, _mm256_set1_ps,
+    _mm256_add_ps, _mm256_sub_ps, _mm256_mul_ps);
+
+impl_simd_kernels!(f64_kernels, f64, 4, _mm256_loadu_pd, _mm256_storeu_pd, _mm256_set1_pd,
+    _mm256_add_pd, _mm256_sub_pd, _mm256_mul_pd);
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    // Lengths that exercise both the vector loop and the scalar tail.
+    fn sample(n: usize) -> Vec<f64>
+    {
+        (0..n).map(|i| (i as f64 * 0.37).sin()).collect()
+    }
+
+    #[test]
+    fn test_kernels_match_scalar_loops() {
+        for n in [0, 1, 3, 4, 7, 16, 33] {
+            let x = sample(n);
+            let y: Vec<f64> = sample(n + 2).into_iter().skip(2).collect();
+
+            let mut out = x.clone();
+            f64_kernels::add_assign(&mut out, &y);
+            assert!(out.iter().zip(x.iter().zip(&y)).all(|(o, (a, b))| *o == a + b));
+
+            let mut out = x.clone();
+            f64_kernels::sub_assign(&mut out, &y);
+            assert!(out.iter().zip(x.iter().zip(&y)).all(|(o, (a, b))| *o == a - b));
+
+            let mut out = x.clone();
+            f64_kernels::scale(&mut out, 0.3);
+            assert!(out.iter().zip(&x).all(|(o, a)| *o == a * 0.3));
+
+            let mut out = x.clone();
+            f64_kernels::axpy(&mut out, -1.7, &y);
+            assert!(out.iter().zip(x.iter().zip(&y)).all(|(o, (a, b))| *o == a + -1.7 * b));
+        }
+    }
+
+    #[test]
+    fn test_f32_kernels_with_mismatched_lengths() {
+        let mut out = vec![1.0f32; 11];
+        f32_kernels::axpy(&mut out, 2.0, &[1.0; 9]);
+        f32_kernels::add_assign(&mut out, &[0.5; 10]);
+
+        assert_eq!(&out[..9], &[3.5f32; 9]);
+        assert_eq!(out[9], 1.5);
+        assert_eq!(out[10], 1.0);
+    }
+}
@@ -1,25 +1,34 @@
-use std::borrow::Borrow;
+use std::borrow::{Borrow, BorrowMut};
 use std::cmp;
-use std::ops::{Deref, DerefMut};
+use std::ops::{Deref, DerefMut, RangeBounds};
 
-use crate::algebra::Algebra;
-use crate::basis::Basis;
-use crate::coefficients::CoefficientField;
+use crate::algebra::{Algebra, MaybeSendSync, ProductFn};
+use crate::coefficients::{ApproxEq, CoefficientRing, CoefficientField, Magnitude};
 use crate::DegreeType;
 
-use crate::vector::{DenseVector, Vector, VectorWithDegree, ResizeableDenseVector};
+use crate::vector::{DegreeProjection, DenseVector, MapCoefficients, Vector, VectorApproxEq, VectorWithDegree, ResizeableDenseVector};
+use crate::vector::{GradedNormedVector, Norm, NormedVector};
 use crate::vector::SimpleDenseVector;
+use crate::vector::implementation::{SimpleDenseIter, SimpleDenseIterMut};
+use crate::vector::ops::{impl_algebra_ops, impl_vector_ops};
 
-use super::super::{TensorBasis};
+use super::super::{TensorBasis, TensorKey, FreeTensor};
+#[cfg(all(feature = "parallel", not(feature = "deterministic")))]
+use super::parallel;
 
 
-#[derive(PartialEq)]
-pub struct DenseTensor<'a, S: CoefficientField, const NLETTERS: DegreeType>
+/// Dense free tensor truncated at degree `DEPTH`.
+///
+/// This is a thin wrapper around a `SimpleDenseVector` over the tensor basis that fixes the
+/// truncation degree at the type level. All of the vector operations are passed through to the
+/// underlying dense vector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DenseTensor<'a, S: CoefficientRing, const NLETTERS: DegreeType, const DEPTH: DegreeType>
     (SimpleDenseVector<'a, TensorBasis<NLETTERS>, S>);
 
 
-impl<'a, S, const NLETTERS: DegreeType> Deref for DenseTensor<'a, S, NLETTERS>
-    where S: CoefficientField
+impl<'a, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> Deref for DenseTensor<'a, S, NLETTERS, DEPTH>
+    where S: CoefficientRing
 {
     type Target = SimpleDenseVector<'a, TensorBasis<NLETTERS>, S>;
 
@@ -29,8 +38,8 @@ impl<'a, S, const NLETTERS: DegreeType> Deref for DenseTensor<'a, S, NLETTERS>
 }
 
 
-impl<'a, S, const NLETTERS: DegreeType> DerefMut for DenseTensor<'a, S, NLETTERS>
-    where S: CoefficientField
+impl<'a, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> DerefMut for DenseTensor<'a, S, NLETTERS, DEPTH>
+    where S: CoefficientRing
 {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
@@ -38,18 +47,18 @@ impl<'a, S, const NLETTERS: DegreeType> DerefMut for DenseTensor<'a, S, NLETTERS
 }
 
 
-impl<'a, S, const NLETTERS: DegreeType> Into<SimpleDenseVector<'a, TensorBasis<NLETTERS>, S>>
-    for DenseTensor<'a, S, NLETTERS>
-        where S: CoefficientField
+impl<'a, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> Into<SimpleDenseVector<'a, TensorBasis<NLETTERS>, S>>
+    for DenseTensor<'a, S, NLETTERS, DEPTH>
+        where S: CoefficientRing
 {
     fn into(self) -> SimpleDenseVector<'a, TensorBasis<NLETTERS>, S> {
         self.0
     }
 }
 
-impl<'a, S, const NLETTERS: DegreeType> From<SimpleDenseVector<'a, TensorBasis<NLETTERS>, S>>
-    for DenseTensor<'a, S, NLETTERS>
-        where S: CoefficientField
+impl<'a, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> From<SimpleDenseVector<'a, TensorBasis<NLETTERS>, S>>
+    for DenseTensor<'a, S, NLETTERS, DEPTH>
+        where S: CoefficientRing
 {
     fn from(arg: SimpleDenseVector<'a, TensorBasis<NLETTERS>, S>) -> Self {
         Self(arg)
@@ -57,36 +66,394 @@ impl<'a, S, const NLETTERS: DegreeType> From<SimpleDenseVector<'a, TensorBasis<N
 }
 
 
-mod tensor_mul_impl {
-    use crate::coefficients::CoefficientField;
-    
+// The underlying vector might have optimised versions of the default methods, so we pass
+// everything through to those.
+impl<'vec, 'a: 'vec, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> Vector<'vec>
+    for DenseTensor<'a, S, NLETTERS, DEPTH>
+        where S: 'static + CoefficientRing
+{
+    type BasisType = TensorBasis<NLETTERS>;
+    type KeyType = TensorKey<NLETTERS>;
+    type ScalarType = S;
 
-    pub(super) unsafe fn dense_tensor_multiply_into_buffer<S: CoefficientField>(
-        out: &mut [S],
-        lhs: &[S],
-        rhs: &[S],
-        func: &mut impl FnMut(&S) -> S,
-    )
+    fn new() -> Self
     {
-        let mut out_ptr = out.as_mut_ptr();
+        Self::from(SimpleDenseVector::new())
+    }
+
+    fn from_key(key: impl Into<Self::KeyType>) -> Self {
+        Self::from(SimpleDenseVector::from_key(key))
+    }
+
+    fn from_key_scalar(key: impl Into<Self::KeyType>, scalar: impl Into<Self::ScalarType>) -> Self {
+        Self::from(SimpleDenseVector::from_key_scalar(key, scalar))
+    }
+
+    fn from_iterator(iterator: impl IntoIterator<Item=(Self::KeyType, Self::ScalarType)>) -> Self {
+        Self::from(SimpleDenseVector::from_iterator(iterator))
+    }
+
+    fn swap(&mut self, mut other: impl BorrowMut<Self>) {
+        self.0.swap(&mut other.borrow_mut().0);
+    }
+
+    fn to_owned(&self) -> Self {
+        Self(Vector::to_owned(&self.0))
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    fn get(&self, key: impl Borrow<Self::KeyType>) -> Option<&Self::ScalarType> {
+        self.0.get(key)
+    }
+
+    fn get_mut(&mut self, key: impl Borrow<Self::KeyType>) -> Option<&mut Self::ScalarType> {
+        self.0.get_mut(key)
+    }
+
+    fn insert_single(&mut self, key: &Self::KeyType, value: impl Into<Self::ScalarType>) {
+        self.0.insert_single(key, value);
+    }
+
+    fn insert(&mut self, iterator: impl IntoIterator<Item=(Self::KeyType, Self::ScalarType)>) {
+        self.0.insert(iterator)
+    }
+
+    fn erase(&mut self, key: impl Borrow<Self::KeyType>) {
+        self.0.erase(key);
+    }
+
+    // The underlying type might have optimised versions of these functions, so we should
+    // pass through to those
+    fn uminus(&self) -> Self {
+        self.0.uminus().into()
+    }
+
+    fn add(&self, other: impl Borrow<Self>) -> Self {
+        self.0.add(&other.borrow().0).into()
+    }
+
+    fn sub(&self, other: impl Borrow<Self>) -> Self {
+        self.0.sub(&other.borrow().0).into()
+    }
+
+    fn scalar_rmultiply(&self, scalar: impl Into<Self::ScalarType>) -> Self {
+        self.0.scalar_rmultiply(scalar).into()
+    }
+
+    fn scalar_lmultiply(&self, scalar: impl Into<Self::ScalarType>) -> Self {
+        self.0.scalar_lmultiply(scalar).into()
+    }
+
+    fn uminus_inplace(&mut self) -> &mut Self {
+        self.0.uminus_inplace();
+        self
+    }
+
+    fn add_inplace(&mut self, other: impl Borrow<Self>) -> &mut Self {
+        self.0.add_inplace(&other.borrow().0);
+        self
+    }
+
+    fn sub_inplace(&mut self, other: impl Borrow<Self>) -> &mut Self {
+        self.0.sub_inplace(&other.borrow().0);
+        self
+    }
+
+    fn scalar_lmultiply_inplace(&mut self, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        self.0.scalar_lmultiply_inplace(scalar);
+        self
+    }
+
+    fn scalar_rmultiply_inplace(&mut self, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        self.0.scalar_rmultiply_inplace(scalar);
+        self
+    }
+
+    fn add_scalar_rmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        self.0.add_scalar_rmultiply(&other.borrow().0, scalar);
+        self
+    }
+
+    fn add_scalar_lmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        self.0.add_scalar_lmultiply(&other.borrow().0, scalar);
+        self
+    }
+
+    fn sub_scalar_rmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        self.0.sub_scalar_rmultiply(&other.borrow().0, scalar);
+        self
+    }
+
+    fn sub_scalar_lmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        self.0.sub_scalar_lmultiply(&other.borrow().0, scalar);
+        self
+    }
+
+    fn scalar_rdivide_inplace_impl(&mut self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        self.0.scalar_rdivide_inplace_impl(rational);
+        self
+    }
+
+    fn add_scalar_rdivide_impl(&mut self, other: &Self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        self.0.add_scalar_rdivide_impl(&other.0, rational);
+        self
+    }
+
+    fn sub_scalar_rdivide_impl(&mut self, other: &Self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        self.0.sub_scalar_rdivide_impl(&other.0, rational);
+        self
+    }
+
+    fn add_linear_combination(&mut self, terms: &[(&Self, Self::ScalarType)]) -> &mut Self {
+        let inner: Vec<_> = terms.iter().map(|(other, scalar)| (&other.0, scalar.clone())).collect();
+        self.0.add_linear_combination(&inner);
+        self
+    }
+}
+
+impl<'vec, 'a: 'vec, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> DenseVector<'vec>
+    for DenseTensor<'a, S, NLETTERS, DEPTH>
+        where S: 'static + CoefficientRing
+{
+    fn as_slice(&self) -> &[S] {
+        DenseVector::as_slice(&self.0)
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [S] {
+        DenseVector::as_mut_slice(&mut self.0)
+    }
+}
+
+impl<'vec, 'a: 'vec, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> ResizeableDenseVector<'vec>
+    for DenseTensor<'a, S, NLETTERS, DEPTH>
+        where S: 'static + CoefficientRing
+{
+    fn resize(&mut self, new_dim: usize) {
+        self.0.resize(new_dim)
+    }
+}
+
+impl<'vec, 'a: 'vec, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> VectorWithDegree<'vec>
+    for DenseTensor<'a, S, NLETTERS, DEPTH>
+        where S: 'static + CoefficientRing
+{
+    fn degree(&self) -> DegreeType {
+        self.0.degree()
+    }
+}
+
+impl<'vec, 'a: 'vec, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> DegreeProjection<'vec>
+    for DenseTensor<'a, S, NLETTERS, DEPTH>
+        where S: 'static + CoefficientRing
+{
+    fn project(&self, degrees: impl RangeBounds<DegreeType>) -> Self {
+        DenseTensor(self.0.project(degrees))
+    }
+
+    fn truncate(&mut self, degree: DegreeType) {
+        self.0.truncate(degree)
+    }
+}
+
+impl<'vec, 'a: 'vec, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> VectorApproxEq<'vec>
+    for DenseTensor<'a, S, NLETTERS, DEPTH>
+        where S: 'static + ApproxEq
+{
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        self.0.approx_eq(&other.0, tol)
+    }
+}
+
+impl<'vec, 'a: 'vec, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> NormedVector<'vec>
+    for DenseTensor<'a, S, NLETTERS, DEPTH>
+        where S: 'static + Magnitude
+{
+    fn norm(&self, norm: Norm) -> f64 {
+        self.0.norm(norm)
+    }
+
+    fn inner_product(&self, other: &Self) -> S {
+        self.0.inner_product(&other.0)
+    }
+}
+
+impl<'vec, 'a: 'vec, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> GradedNormedVector<'vec>
+    for DenseTensor<'a, S, NLETTERS, DEPTH>
+        where S: 'static + Magnitude
+{
+    fn degree_norm(&self, degree: DegreeType, norm: Norm) -> f64 {
+        self.0.degree_norm(degree, norm)
+    }
+}
+
+impl<'vec, 'a: 'vec, S, T, const NLETTERS: DegreeType, const DEPTH: DegreeType> MapCoefficients<'vec, T>
+    for DenseTensor<'a, S, NLETTERS, DEPTH>
+        where S: 'static + CoefficientRing,
+              T: 'static + CoefficientRing
+{
+    type Output = DenseTensor<'static, T, NLETTERS, DEPTH>;
+
+    fn map_coefficients(&self, func: impl FnMut(&S) -> T) -> Self::Output {
+        DenseTensor(self.0.map_coefficients(func))
+    }
+}
+
+impl<'vec, 'a: 'vec, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> FreeTensor<'vec, S, NLETTERS>
+    for DenseTensor<'a, S, NLETTERS, DEPTH>
+        where S: 'static + CoefficientField + MaybeSendSync,
+              S::RationalType: MaybeSendSync
+{
+    const MAX_DEGREE: DegreeType = DEPTH;
+}
+
+impl<'v, 'a, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> IntoIterator
+    for &'v DenseTensor<'a, S, NLETTERS, DEPTH>
+        where S: CoefficientRing
+{
+    type Item = (TensorKey<NLETTERS>, &'v S);
+    type IntoIter = SimpleDenseIter<'v, TensorBasis<NLETTERS>, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'v, 'a, S, const NLETTERS: DegreeType, const DEPTH: DegreeType> IntoIterator
+    for &'v mut DenseTensor<'a, S, NLETTERS, DEPTH>
+        where S: CoefficientRing
+{
+    type Item = (TensorKey<NLETTERS>, &'v mut S);
+    type IntoIter = SimpleDenseIterMut<'v, TensorBasis<NLETTERS>, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
 
-        for lhs_v in lhs {
-            for rhs_v in rhs {
-                S::add_inplace(&mut *out_ptr, &func(&S::mul(lhs_v, rhs_v)));
-                out_ptr = out_ptr.add(1);
+impl_vector_ops!(['a, S, const NLETTERS: DegreeType, const DEPTH: DegreeType]
+    DenseTensor<'a, S, NLETTERS, DEPTH>, S, [S: 'static + CoefficientRing]);
+
+impl_algebra_ops!(['a, S, const NLETTERS: DegreeType, const DEPTH: DegreeType]
+    DenseTensor<'a, S, NLETTERS, DEPTH>, DEPTH, [S: 'static + CoefficientRing + MaybeSendSync]);
+
+
+/// Cache-blocked kernels for the dense tensor product.
+///
+/// The words of degree `out_deg` are split into tiles of words sharing their first `prefix`
+/// letters, each holding at most `TILE_SIZE` coefficients. For a lhs degree at least `prefix`,
+/// the products landing in a tile come from a contiguous run of lhs words and the whole rhs
+/// degree block; otherwise they come from a single lhs word and a contiguous run of rhs words
+/// as long as the tile. Either way the operands are no larger than the tile, so all of the
+/// products for a tile are accumulated while it is in cache, rather than streaming the whole
+/// output block once for each lhs degree.
+///
+/// Tiling only changes the order in which distinct coefficients are visited. Each coefficient
+/// receives its contributions in the same order as in the untiled loop, so the results are
+/// bit-identical to it for every tile size.
+pub(super) mod tensor_mul_impl {
+    use std::ops::Range;
+
+    use crate::coefficients::CoefficientRing;
+    use crate::free_tensor::TensorBasis;
+    use crate::{DegreeType, DimensionType};
+
+    /// Largest number of output coefficients in a tile. With 8 byte coefficients a tile and
+    /// its operands take at most 96KiB, which fits in L2.
+    pub(crate) const TILE_SIZE: DimensionType = 1 << 12;
+
+    /// The words of degree `out_deg` whose first `prefix` letters have index `index`.
+    #[derive(Debug, Clone, Copy)]
+    pub(crate) struct Tile<const NLETTERS: DegreeType> {
+        out_deg: DegreeType,
+        prefix: DegreeType,
+        index: DimensionType,
+        len: DimensionType,
+    }
+
+    impl<const NLETTERS: DegreeType> Tile<NLETTERS> {
+
+        /// Split `out`, the degree `out_deg` block of a tensor, into tiles of at most
+        /// `tile_size` coefficients.
+        pub(crate) fn split<S>(out: &mut [S], out_deg: DegreeType, tile_size: DimensionType)
+            -> impl Iterator<Item=(Self, &mut [S])>
+        {
+            let width = NLETTERS as DimensionType;
+            let mut prefix = out_deg;
+            let mut len = 1;
+            while prefix > 0 && len * width <= tile_size {
+                prefix -= 1;
+                len *= width;
+            }
+
+            out.chunks_mut(len)
+                .enumerate()
+                .map(move |(index, out)| (Tile { out_deg, prefix, index, len }, out))
+        }
+
+        /// Indices of the lhs coefficients of degree `lhs_d`, and of the rhs coefficients of
+        /// the complementary degree, whose products land in the tile.
+        fn operands(&self, lhs_d: DegreeType) -> (Range<DimensionType>, Range<DimensionType>)
+        {
+            let width = NLETTERS as DimensionType;
+            let lhs_range = TensorBasis::<NLETTERS>::degree_range(lhs_d);
+            let rhs_range = TensorBasis::<NLETTERS>::degree_range(self.out_deg - lhs_d);
+
+            if lhs_d >= self.prefix {
+                let len = width.pow(lhs_d - self.prefix);
+                let start = lhs_range.start + self.index * len;
+                (start..start + len, rhs_range)
+            } else {
+                let split = width.pow(self.prefix - lhs_d);
+                let lhs_start = lhs_range.start + self.index / split;
+                let rhs_start = rhs_range.start + (self.index % split) * self.len;
+                (lhs_start..lhs_start + 1, rhs_start..rhs_start + self.len)
             }
         }
     }
 
-    pub(super) unsafe fn dense_tensor_multiply_inplace<S: CoefficientField>(
+    /// Accumulate the part of the product of `lhs` and `rhs` lying in `tile` into `out`, the
+    /// coefficients of the tile, taking the lhs degrees in the order given.
+    ///
+    /// If `scale` is given, each coefficient of `out` is first replaced by `func(v * s)` for
+    /// each `s` in `scale`, which computes the product with the degree 0 part of the rhs in
+    /// place. If `func` is `None`, the products are used as they are.
+    pub(crate) fn dense_tensor_multiply_tile<S, F, const NLETTERS: DegreeType>(
         out: &mut [S],
+        tile: &Tile<NLETTERS>,
+        lhs: &[S],
         rhs: &[S],
-        func: &mut impl FnMut(&S) -> S
+        lhs_degrees: impl Iterator<Item=DegreeType>,
+        scale: Option<&[S]>,
+        mut func: Option<&mut F>,
     )
+        where S: CoefficientRing,
+              F: FnMut(&S) -> S
     {
-        for out_ptr in out {
-            for rhs_v in rhs {
-                *out_ptr = func(&S::mul(out_ptr, rhs_v));
+        // Scratch space for the mapped kernels, shared by all the lhs degrees of the tile.
+        let mut buffer = Vec::new();
+
+        for rhs_v in scale.into_iter().flatten() {
+            match func.as_deref_mut() {
+                Some(func) => S::mul_map_slice_inplace(out, rhs_v, func),
+                None => S::mul_slice_inplace(out, rhs_v),
+            }
+        }
+
+        for lhs_d in lhs_degrees {
+            let (lhs_range, rhs_range) = tile.operands(lhs_d);
+            let (lhs, rhs) = (&lhs[lhs_range], &rhs[rhs_range]);
+            match func.as_deref_mut() {
+                Some(func) => S::outer_product_map_add_inplace(out, lhs, rhs, func, &mut buffer),
+                None => S::outer_product_add_inplace(out, lhs, rhs),
             }
         }
     }
@@ -178,8 +545,8 @@ impl<'a, S, const NLETTERS: DegreeType> Algebra for DenseTensor<'a, S, NLETTERS>
         }
 
         let (offset, assign) = match rhs_r.as_slice().get(0) {
-            Some(val) if *val == S::ZERO => (1, true),
-            Some(val) if *val == S::ONE => (1, false),
+            Some(val) if *val == S::zero() => (1, true),
+            Some(val) if *val == S::one() => (1, false),
             _ => (0, true)
         };
 
@@ -256,7 +623,7 @@ impl<'a, S, const NLETTERS: DegreeType> Algebra for DenseTensor<'a, S, NLETTERS>
 */
 
 impl<'vec, V, S, const NLETTERS: DegreeType> Algebra<'vec> for V
-    where S: CoefficientField,
+    where S: CoefficientRing + MaybeSendSync,
           V: ResizeableDenseVector<'vec, BasisType=TensorBasis<NLETTERS>, ScalarType=S>
              + VectorWithDegree<'vec>
 {
@@ -265,158 +632,187 @@ impl<'vec, V, S, const NLETTERS: DegreeType> Algebra<'vec> for V
         &mut self,
         lhs: impl Borrow<Self>,
         rhs: impl Borrow<Self>,
-        mut func: impl FnMut(&<Self as Vector<'vec>>::ScalarType) -> <Self as Vector<'vec>>::ScalarType,
+        mut func: impl ProductFn<<Self as Vector<'vec>>::ScalarType>,
         to_degree: Option<DegreeType>
     )
     {
-        use tensor_mul_impl::dense_tensor_multiply_into_buffer;
-
-        let rhs_r = rhs.borrow();
-        let lhs_r = lhs.borrow();
-
-        let lhs_deg = lhs_r.degree();
-        let rhs_deg = rhs_r.degree();
-
-        let max_deg = cmp::min(
-            to_degree.expect("Max degree should be set for tensor types"),
-            lhs_deg + rhs_deg
-        );
-
-        if self.degree() < max_deg {
-            self.resize(TensorBasis::<NLETTERS>::start_of_degree(max_deg+1));
-        }
-
-
-        for out_deg in (0..=max_deg).rev() {
-            // Notice that out_deg >= rhs_deg, out_deg - rhs_deg >= 0
-            //cmp::max(0, (out_deg - rhs_deg));
-            let lhs_deg_min = {
-                if out_deg > rhs_deg {
-                    out_deg - rhs_deg
-                } else {
-                    0
-                }
-            };
-            let lhs_deg_max = cmp::min(out_deg, lhs_deg);
-
-            for lhs_d in (lhs_deg_min..=lhs_deg_max).rev() {
-                let rhs_d = out_deg - lhs_d;
-                unsafe {
-                    dense_tensor_multiply_into_buffer(
-                        self.as_mut_slice()
-                            .get_unchecked_mut(
-                                TensorBasis::<NLETTERS>::degree_range(out_deg)),
-                        lhs_r.as_slice()
-                            .get_unchecked(
-                                TensorBasis::<NLETTERS>::degree_range(lhs_d)),
-                        rhs_r.as_slice()
-                            .get_unchecked(
-                                TensorBasis::<NLETTERS>::degree_range(rhs_d)),
-                        &mut func
-                    );
-                }
-            }
-        }
+        dense_multiply_and_add_into(self, lhs.borrow(), rhs.borrow(), Some(&mut func), to_degree);
     }
 
     fn multiply_into_impl(
         &mut self,
         rhs: impl Borrow<Self>,
-        mut func: impl FnMut(&<Self as Vector<'vec>>::ScalarType) -> <Self as Vector<'vec>>::ScalarType,
+        mut func: impl ProductFn<<Self as Vector<'vec>>::ScalarType>,
         to_degree: Option<DegreeType>
     )
     {
-        use tensor_mul_impl::*;
-
-        let rhs_r = rhs.borrow();
-
-        let lhs_deg = self.degree();
-        let rhs_deg = rhs_r.degree();
+        dense_multiply_into(self, rhs.borrow(), Some(&mut func), to_degree);
+    }
 
-        let max_deg = cmp::min(
-            to_degree.expect("Max degree should be set for tensor types"),
-            lhs_deg + rhs_deg
-        );
+    fn multiply_and_add_into_unmapped_impl(
+        &mut self,
+        lhs: impl Borrow<Self>,
+        rhs: impl Borrow<Self>,
+        to_degree: Option<DegreeType>
+    )
+    {
+        dense_multiply_and_add_into(self, lhs.borrow(), rhs.borrow(), None::<&mut fn(&S) -> S>, to_degree);
+    }
 
-        if self.degree() < max_deg {
-            self.resize(TensorBasis::<NLETTERS>::start_of_degree(max_deg+1));
-        }
+    fn multiply_into_unmapped_impl(
+        &mut self,
+        rhs: impl Borrow<Self>,
+        to_degree: Option<DegreeType>
+    )
+    {
+        dense_multiply_into(self, rhs.borrow(), None::<&mut fn(&S) -> S>, to_degree);
+    }
 
-        let (offset, assign) = match rhs_r.as_slice().get(0) {
-            Some(val) if *val == S::ZERO => (1, true),
-            Some(val) if *val == S::ONE => (1, false),
-            _ => (0, true)
-        };
+}
 
-        for out_deg in (1..=max_deg).rev() {
-            let lhs_deg_min = cmp::max(0, out_deg - rhs_deg);
 
-            let lhs_deg_max = cmp::min(out_deg - offset, lhs_deg);
+// The products of the dense Algebra implementation. When func is None, the products are added
+// as they are, which lets the float kernels skip the call for each product.
+fn dense_multiply_and_add_into<'vec, V, S, F, const NLETTERS: DegreeType>(
+    out: &mut V,
+    lhs: &V,
+    rhs: &V,
+    mut func: Option<&mut F>,
+    to_degree: Option<DegreeType>
+)
+    where S: CoefficientRing + MaybeSendSync,
+          V: ResizeableDenseVector<'vec, BasisType=TensorBasis<NLETTERS>, ScalarType=S>
+             + VectorWithDegree<'vec>,
+          F: ProductFn<S>
+{
+    use tensor_mul_impl::{dense_tensor_multiply_tile, Tile, TILE_SIZE};
 
-            let mut reduce = 0;
+    // The product with a zero vector is zero, so there is nothing to add.
+    if lhs.as_slice().is_empty() || rhs.as_slice().is_empty() {
+        return;
+    }
 
-            if assign {
-                reduce = 1;
+    let lhs_deg = lhs.degree();
+    let rhs_deg = rhs.degree();
 
+    let max_deg = cmp::min(
+        to_degree.expect("Max degree should be set for tensor types"),
+        lhs_deg + rhs_deg
+    );
 
-                let rhs_deg_range =
-                    TensorBasis::<NLETTERS>::degree_range(out_deg - lhs_deg_max);
-                let out_deg_range =
-                    TensorBasis::<NLETTERS>::degree_range(out_deg);
+    let required_size = TensorBasis::<NLETTERS>::start_of_degree(max_deg+1);
+    if out.as_slice().len() < required_size {
+        out.resize(required_size);
+    }
 
-                if offset == 1 {
-                    let lhs_deg_range =
-                        TensorBasis::<NLETTERS>::degree_range(lhs_deg_max);
-                    let lhs_as_slice = self.as_mut_slice();
-                    let (a, b) = lhs_as_slice.split_at_mut(
-                        TensorBasis::<NLETTERS>::start_of_degree(lhs_deg_max));
+    #[cfg(all(feature = "parallel", not(feature = "deterministic")))]
+    {
+        if required_size >= parallel::MIN_PARALLEL_DIMENSION {
+            parallel::dense_tensor_multiply_and_add::<S, F, NLETTERS>(
+                &mut out.as_mut_slice()[..required_size],
+                lhs.as_slice(), lhs_deg, rhs.as_slice(), rhs_deg, func.as_deref());
+            return;
+        }
+    }
 
-                    unsafe {
-                        dense_tensor_multiply_into_buffer(
-                            b.get_unchecked_mut(out_deg_range),
-                            a.get_unchecked(lhs_deg_range),
-                            rhs_r.as_slice().get_unchecked(rhs_deg_range),
-                            &mut func,
-                        );
-                    }
-                } else if offset == 0 {
-                    unsafe {
-                        dense_tensor_multiply_inplace(
-                            self.as_mut_slice().get_unchecked_mut(out_deg_range),
-                            rhs_r.as_slice().get_unchecked(rhs_deg_range),
-                            &mut func,
-                        );
-                    }
-                }
+    for out_deg in (0..=max_deg).rev() {
+        // Notice that out_deg >= rhs_deg, out_deg - rhs_deg >= 0
+        //cmp::max(0, (out_deg - rhs_deg));
+        let lhs_deg_min = {
+            if out_deg > rhs_deg {
+                out_deg - rhs_deg
+            } else {
+                0
             }
+        };
+        let lhs_deg_max = cmp::min(out_deg, lhs_deg);
+
+        let out = &mut out.as_mut_slice()[TensorBasis::<NLETTERS>::degree_range(out_deg)];
+        for (tile, out_tile) in Tile::<NLETTERS>::split(out, out_deg, TILE_SIZE) {
+            dense_tensor_multiply_tile(
+                out_tile,
+                &tile,
+                lhs.as_slice(),
+                rhs.as_slice(),
+                (lhs_deg_min..=lhs_deg_max).rev(),
+                None,
+                func.as_deref_mut()
+            );
+        }
+    }
+}
 
+fn dense_multiply_into<'vec, V, S, F, const NLETTERS: DegreeType>(
+    out: &mut V,
+    rhs: &V,
+    mut func: Option<&mut F>,
+    to_degree: Option<DegreeType>
+)
+    where S: CoefficientRing + MaybeSendSync,
+          V: ResizeableDenseVector<'vec, BasisType=TensorBasis<NLETTERS>, ScalarType=S>
+             + VectorWithDegree<'vec>,
+          F: ProductFn<S>
+{
+    use tensor_mul_impl::{dense_tensor_multiply_tile, Tile, TILE_SIZE};
 
-            for lhs_d in (lhs_deg_min..=(lhs_deg_max - reduce)).rev() {
-                let rhs_d = out_deg - lhs_d;
+    if out.as_slice().is_empty() {
+        return;
+    }
+    if rhs.as_slice().is_empty() {
+        out.clear();
+        return;
+    }
 
-                let rhs_deg_range =
-                    TensorBasis::<NLETTERS>::degree_range(out_deg - lhs_d);
-                let out_deg_range =
-                    TensorBasis::<NLETTERS>::degree_range(rhs_d);
-                let lhs_deg_range =
-                    TensorBasis::<NLETTERS>::degree_range(lhs_d);
+    let lhs_deg = out.degree();
+    let rhs_deg = rhs.degree();
 
-                let lhs_as_slice = self.as_mut_slice();
-                let (a, b) = lhs_as_slice.split_at_mut(
-                    TensorBasis::<NLETTERS>::start_of_degree(lhs_deg_max));
-                unsafe {
-                    dense_tensor_multiply_into_buffer(
-                        b.get_unchecked_mut(out_deg_range),
-                        a.get_unchecked(lhs_deg_range),
-                        rhs_r.as_slice().get_unchecked(rhs_deg_range),
-                        &mut func,
-                    );
-                }
-            }
+    let max_deg = cmp::min(
+        to_degree.expect("Max degree should be set for tensor types"),
+        lhs_deg + rhs_deg
+    );
+
+    // Anything above the truncation degree is discarded by the product.
+    out.resize(TensorBasis::<NLETTERS>::start_of_degree(max_deg+1));
 
+    #[cfg(all(feature = "parallel", not(feature = "deterministic")))]
+    {
+        if out.as_slice().len() >= parallel::MIN_PARALLEL_DIMENSION {
+            parallel::dense_tensor_multiply_inplace::<S, F, NLETTERS>(
+                out.as_mut_slice(), lhs_deg, rhs.as_slice(), rhs_deg, max_deg, func.as_deref());
+            return;
         }
     }
 
+    // The output is computed from the top degree downwards, so that when computing degree
+    // out_deg, all the lower degree components of out still hold their original values.
+    for out_deg in (0..=max_deg).rev() {
+        let out_deg_range = TensorBasis::<NLETTERS>::degree_range(out_deg);
+        let (lower, upper) = out.as_mut_slice().split_at_mut(out_deg_range.start);
+
+        // The degree 0 part of rhs multiplies the current degree in place. This must
+        // happen before the lower degree contributions are accumulated.
+        let scale = if out_deg <= lhs_deg {
+            Some(&rhs.as_slice()[TensorBasis::<NLETTERS>::degree_range(0)])
+        } else {
+            None
+        };
+
+        let lhs_deg_min = out_deg.saturating_sub(rhs_deg);
+        let lhs_degrees = (lhs_deg_min..cmp::min(out_deg, lhs_deg + 1)).rev();
+
+        let out = &mut upper[..out_deg_range.len()];
+        for (tile, out_tile) in Tile::<NLETTERS>::split(out, out_deg, TILE_SIZE) {
+            dense_tensor_multiply_tile(
+                out_tile,
+                &tile,
+                lower,
+                rhs.as_slice(),
+                lhs_degrees.clone(),
+                scale,
+                func.as_deref_mut()
+            );
+        }
+    }
 }
 
 
@@ -426,6 +822,8 @@ impl<'vec, V, S, const NLETTERS: DegreeType> Algebra<'vec> for V
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::basis::OrderedBasis;
+    use crate::coefficients::{Complex, DoubleDouble, Dual, Interval, Matrix, ModP, Monomial, Polynomial, Rational};
     use crate::free_tensor::TensorKey;
 
     type BasisT = TensorBasis<3>;
@@ -446,7 +844,577 @@ mod tests {
 
     }
 
+    #[test]
+    fn test_multiply_inplace_matches_multiply() {
+        let lhs = TensorT::from_iterator(vec![
+            (Key::new(), 1.0),
+            (Key::from_letter(1), 2.0),
+            (Key::from_letter(3), 3.0),
+            (Key::from_letters(&[2, 1]), -1.0),
+        ]);
+        let rhs = TensorT::from_iterator(vec![
+            (Key::new(), 0.5),
+            (Key::from_letter(2), 5.0),
+            (Key::from_letters(&[1, 3]), 7.0),
+        ]);
+
+        let expected = lhs.multiply(&rhs, Some(3));
+
+        let mut result = lhs.clone();
+        result.multiply_inplace(&rhs, Some(3));
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_multiply_inplace_truncates() {
+        let mut lhs = TensorT::from_key(Key::from_letters(&[1, 2]));
+        let rhs = TensorT::from_key(Key::from_letter(3));
+
+        lhs.multiply_inplace(&rhs, Some(2));
+
+        assert_eq!(lhs, TensorT::new());
+    }
+
+    #[test]
+    fn test_multiply_by_zero_vector() {
+        let lhs = TensorT::from_key(Key::from_letter(1));
+        let zero = TensorT::new();
+
+        assert_eq!(lhs.multiply(&zero, Some(2)), TensorT::new());
+        assert_eq!(zero.multiply(&lhs, Some(2)), TensorT::new());
+    }
+
+    #[test]
+    fn test_integer_coefficients_count_words() {
+        type IntTensor<'a> = SimpleDenseVector<'a, TensorBasis<2>, i64>;
+        type IntKey = TensorKey<2>;
+
+        let x = IntTensor::from_iterator(vec![
+            (IntKey::from_letter(1), 1),
+            (IntKey::from_letter(2), 1),
+        ]);
+
+        let mut result = x.multiply(&x, Some(3));
+        result.multiply_inplace(&x, Some(3));
+
+        // Every word of length 3 appears exactly once in (1 + 2)^3
+        let expected = IntTensor::from_iterator(
+            TensorBasis::<2>::iter_keys()
+                .skip_while(|k| k.size() < 3)
+                .take_while(|k| k.size() == 3)
+                .map(|k| (k, 1))
+        );
+
+        assert_eq!(result, expected);
+    }
+
 
+    #[test]
+    fn test_log_of_exp_f64_is_approximately_identity() {
+        let x = DenseTensor::<f64, 2, 4>::from_iterator(vec![
+            (RatKey::from_letter(1), 0.1),
+            (RatKey::from_letter(2), -0.7),
+            (RatKey::from_letters(&[1, 2]), 0.3),
+        ]);
+
+        let result = x.exp().log();
+
+        assert!(result.approx_eq_default(&x));
+        assert!(!result.approx_eq_default(&x.scalar_rmultiply(1.001)));
+    }
 
 
-}
\ No newline at end of file
+    type RatTensor<'a> = DenseTensor<'a, Rational, 2, 4>;
+    type RatKey = TensorKey<2>;
+
+    fn rat(num: i64, den: i64) -> Rational {
+        Rational::new(num, den)
+    }
+
+    #[test]
+    fn test_exp_single_letter() {
+        let x = RatTensor::from_key(RatKey::from_letter(1));
+
+        let expected = RatTensor::from_iterator(vec![
+            (RatKey::new(), rat(1, 1)),
+            (RatKey::from_letter(1), rat(1, 1)),
+            (RatKey::from_letters(&[1, 1]), rat(1, 2)),
+            (RatKey::from_letters(&[1, 1, 1]), rat(1, 6)),
+            (RatKey::from_letters(&[1, 1, 1, 1]), rat(1, 24)),
+        ]);
+
+        assert_eq!(x.exp(), expected);
+    }
+
+    #[test]
+    fn test_log_of_exp_is_identity() {
+        let x = RatTensor::from_iterator(vec![
+            (RatKey::from_letter(1), rat(1, 2)),
+            (RatKey::from_letter(2), rat(-3, 7)),
+            (RatKey::from_letters(&[1, 2]), rat(2, 3)),
+            (RatKey::from_letters(&[2, 1]), rat(-2, 3)),
+        ]);
+
+        assert_eq!(x.exp().log(), x);
+    }
+
+    #[test]
+    fn test_fmexp_matches_multiply_exp() {
+        let a = RatTensor::from_iterator(vec![
+            (RatKey::new(), rat(1, 1)),
+            (RatKey::from_letter(2), rat(5, 2)),
+        ]);
+        let x = RatTensor::from_iterator(vec![
+            (RatKey::from_letter(1), rat(1, 3)),
+            (RatKey::from_letter(2), rat(1, 5)),
+        ]);
+
+        let expected = a.multiply(x.exp(), Some(4));
+
+        let mut result = a.clone();
+        result.fmexp(&x);
+
+        assert_eq!(result, expected);
+    }
+
+
+    type PolyTensor<'a> = DenseTensor<'a, Polynomial<Rational>, 2, 3>;
+
+    fn symbolic_increment() -> PolyTensor<'static> {
+        PolyTensor::from_iterator(vec![
+            (RatKey::from_letter(1), Polynomial::indeterminate("a")),
+            (RatKey::from_letter(2), Polynomial::indeterminate("b")),
+        ])
+    }
+
+    #[test]
+    fn test_exp_symbolic_increment() {
+        let sig = symbolic_increment().exp();
+
+        let a = Monomial::from_indeterminate("a");
+        let b = Monomial::from_indeterminate("b");
+
+        let expected_12 = Polynomial::from_term(a.mul(&b), rat(1, 2));
+        let expected_112 = Polynomial::from_term(a.mul(&a).mul(&b), rat(1, 6));
+
+        assert_eq!(sig.get(&RatKey::from_letters(&[1, 2])), Some(&expected_12));
+        assert_eq!(sig.get(&RatKey::from_letters(&[2, 1])), Some(&expected_12));
+        assert_eq!(sig.get(&RatKey::from_letters(&[1, 1, 2])), Some(&expected_112));
+    }
+
+    #[test]
+    fn test_log_of_exp_symbolic_is_identity() {
+        let x = symbolic_increment();
+
+        assert_eq!(x.exp().log(), x);
+    }
+
+
+    type ComplexTensor<'a> = DenseTensor<'a, Complex<f64>, 2, 4>;
+
+    #[test]
+    fn test_exp_imaginary_letter() {
+        let x = ComplexTensor::from_key_scalar(RatKey::from_letter(1), Complex::<f64>::i());
+        let sig = x.exp();
+
+        // exp(i e1) has coefficient i^k / k! on the word 1...1 of length k
+        let expected = [
+            Complex::new(1.0, 0.0),
+            Complex::new(0.0, 1.0),
+            Complex::new(-0.5, 0.0),
+            Complex::new(0.0, -1.0 / 6.0),
+            Complex::new(1.0 / 24.0, 0.0),
+        ];
+
+        for (deg, val) in expected.iter().enumerate() {
+            let key = RatKey::from_letters(&vec![1; deg]);
+            let coeff = sig.get(&key).copied().unwrap();
+            assert!(coeff.sub(val).norm() < 1e-15, "{:?} != {:?}", coeff, val);
+        }
+        assert_eq!(sig.get(&RatKey::from_letters(&[1, 2])), Some(&Complex::zero()));
+    }
+
+
+    type DualTensor<'a> = DenseTensor<'a, Dual<Rational, 2>, 2, 3>;
+
+    #[test]
+    fn test_exp_dual_derivatives() {
+        // Increment a*e1 + b*e2 at a = 1/2, b = 3, differentiating with respect to a and b
+        let x = DualTensor::from_iterator(vec![
+            (RatKey::from_letter(1), Dual::variable(rat(1, 2), 0)),
+            (RatKey::from_letter(2), Dual::variable(rat(3, 1), 1)),
+        ]);
+
+        let sig = x.exp();
+
+        // Coefficient of e12 is ab/2 and coefficient of e112 is a^2 b/6
+        let c12 = sig.get(&RatKey::from_letters(&[1, 2])).unwrap();
+        assert_eq!(*c12.value(), rat(3, 4));
+        assert_eq!(c12.derivatives(), &[rat(3, 2), rat(1, 4)]);
+
+        let c112 = sig.get(&RatKey::from_letters(&[1, 1, 2])).unwrap();
+        assert_eq!(*c112.value(), rat(1, 8));
+        assert_eq!(c112.derivatives(), &[rat(1, 2), rat(1, 24)]);
+    }
+
+    #[test]
+    fn test_convert_tensor_coefficients() {
+        let x = DualTensor::from_iterator(vec![
+            (RatKey::from_letter(1), Dual::variable(rat(1, 2), 0)),
+            (RatKey::from_letter(2), Dual::variable(rat(3, 1), 1)),
+        ]);
+
+        let primal: DenseTensor<Rational, 2, 3> = x.exp().convert_coefficients();
+        let expected = DenseTensor::<Rational, 2, 3>::from_iterator(vec![
+            (RatKey::from_letter(1), rat(1, 2)),
+            (RatKey::from_letter(2), rat(3, 1)),
+        ]).exp();
+        assert_eq!(primal.as_slice(), expected.as_slice());
+
+        let approx: DenseTensor<f64, 2, 3> = expected.convert_coefficients();
+        assert_eq!(approx.get(&RatKey::from_letters(&[1, 2])), Some(&0.75));
+    }
+
+
+    type ModPTensor<'a> = DenseTensor<'a, ModP<1_000_000_007>, 2, 4>;
+
+    #[test]
+    fn test_log_of_exp_modular_is_identity() {
+        let x = ModPTensor::from_iterator(vec![
+            (RatKey::from_letter(1), ModP::from(3i8)),
+            (RatKey::from_letter(2), ModP::from(-5i8)),
+            (RatKey::from_letters(&[1, 2]), ModP::from(7i8)),
+        ]);
+
+        let sig = x.exp();
+
+        // (3 e1)^4 / 4! contributes 81/24 = 27/8 to the word 1111
+        let expected = ModP::from(27i8).div(&ModP::from(8i8));
+        assert_eq!(sig.get(&RatKey::from_letters(&[1, 1, 1, 1])), Some(&expected));
+        assert_eq!(sig.log(), x);
+    }
+
+
+    #[test]
+    fn test_interval_exp_encloses_exact() {
+        let exact = RatTensor::from_iterator(vec![
+            (RatKey::from_letter(1), rat(1, 3)),
+            (RatKey::from_letter(2), rat(-2, 7)),
+        ]).exp();
+
+        let approx = DenseTensor::<Interval, 2, 4>::from_iterator(vec![
+            (RatKey::from_letter(1), Interval::from(1.0).div(&3.0)),
+            (RatKey::from_letter(2), Interval::from(-2.0).div(&7.0)),
+        ]).exp();
+
+        for key in TensorBasis::<2>::iter_keys().take_while(|k| k.size() <= 4) {
+            let enclosure = approx.get(&key).unwrap();
+            let val = exact.get(&key).unwrap().to_f64();
+            assert!(enclosure.contains(val), "{} not in {} for {:?}", val, enclosure, key);
+            assert!(enclosure.width() < 1e-14);
+        }
+    }
+
+
+    #[test]
+    fn test_log_of_exp_double_double() {
+        let x = DenseTensor::<DoubleDouble, 2, 4>::from_iterator(vec![
+            (RatKey::from_letter(1), DoubleDouble::from_rational(1i8, 3i8)),
+            (RatKey::from_letter(2), DoubleDouble::from_rational(-2i8, 7i8)),
+            (RatKey::from_letters(&[1, 2]), DoubleDouble::from_rational(5i8, 11i8)),
+        ]);
+
+        let result = x.exp().log();
+
+        for key in TensorBasis::<2>::iter_keys().take_while(|k| k.size() <= 4) {
+            let expected = x.get(&key).copied().unwrap_or_else(DoubleDouble::zero);
+            let err = result.get(&key).unwrap().sub(&expected);
+            assert!(err.abs().to_f64() < 1e-30, "error {} for {:?}", err, key);
+        }
+    }
+
+
+    type Mat = Matrix<Rational, 2>;
+    type MatrixTensor<'a> = DenseTensor<'a, Mat, 2, 3>;
+
+    fn mat(rows: [[i64; 2]; 2]) -> Mat {
+        Mat::from_fn(|i, j| Rational::from(rows[i][j]))
+    }
+
+    #[test]
+    fn test_matrix_coefficients_keep_order() {
+        let a = mat([[0, 1], [0, 0]]);
+        let b = mat([[0, 0], [1, 0]]);
+
+        let lhs = MatrixTensor::from_key_scalar(RatKey::from_letter(1), a.clone());
+        let rhs = MatrixTensor::from_key_scalar(RatKey::from_letter(2), b.clone());
+
+        let product = lhs.multiply(&rhs, Some(3));
+        assert_eq!(product.get(&RatKey::from_letters(&[1, 2])), Some(&a.mul(&b)));
+
+        // Left and right scalar multiplication differ for non-commuting scalars
+        let left = lhs.scalar_lmultiply(b.clone());
+        let right = lhs.scalar_rmultiply(b.clone());
+        assert_eq!(left.get(&RatKey::from_letter(1)), Some(&b.mul(&a)));
+        assert_eq!(right.get(&RatKey::from_letter(1)), Some(&a.mul(&b)));
+    }
+
+    #[test]
+    fn test_exp_matrix_coefficients() {
+        let a = mat([[0, 1], [0, 0]]);
+        let b = mat([[0, 0], [1, 0]]);
+
+        let x = MatrixTensor::from_iterator(vec![
+            (RatKey::from_letter(1), a.clone()),
+            (RatKey::from_letter(2), b.clone()),
+        ]);
+
+        let sig = x.exp();
+
+        let two = Rational::from(2i8);
+        assert_eq!(sig.get(&RatKey::from_letters(&[1, 2])), Some(&a.mul(&b).div(&two)));
+        assert_eq!(sig.get(&RatKey::from_letters(&[2, 1])), Some(&b.mul(&a).div(&two)));
+        assert_eq!(sig.log(), x);
+    }
+
+    #[test]
+    fn test_operators_match_algebra_methods() {
+        type Tensor = DenseTensor<'static, f64, 2, 3>;
+        type TKey = TensorKey<2>;
+
+        let x = Tensor::from_iterator(vec![(TKey::from_letter(1), 1.0), (TKey::from_letter(2), 2.0)]);
+        let unit = Tensor::from_key(TKey::new());
+
+        assert_eq!(&x * &x, x.multiply(&x, Some(3)));
+
+        let x2 = &x * &x;
+        let series = &unit + &x + &x2 / 2i8 + x2 * &x / 6i8;
+        assert_eq!(series, x.exp());
+
+        let mut y = x.clone();
+        y *= &x;
+        y *= 0.5;
+        y -= x.multiply(&x, Some(3)) * 0.5;
+        assert_eq!(y, Tensor::new());
+    }
+
+    #[test]
+    fn test_iterate_nonzero_terms_by_word() {
+        type Tensor = DenseTensor<'static, f64, 2, 2>;
+        type TKey = TensorKey<2>;
+
+        let x = Tensor::from_iterator(vec![(TKey::from_letter(1), 1.0), (TKey::from_letter(2), 2.0)]);
+        let mut sig = x.exp();
+
+        let terms: Vec<String> = sig.iter_nonzero()
+            .map(|(k, v)| format!("{}{}", v, k))
+            .collect();
+        assert_eq!(terms, vec!["1()", "1(1)", "2(2)", "0.5(1, 1)", "1(1, 2)", "1(2, 1)", "2(2, 2)"]);
+
+        // Clear the words of length two starting with the letter 2.
+        for (k, v) in &mut sig {
+            if k.size() == 2 && k.get_letter(1) == 2 {
+                *v = 0.0;
+            }
+        }
+        let words: Vec<TKey> = (&sig).into_iter().filter(|(_, v)| **v != 0.0).map(|(k, _)| k).collect();
+        assert_eq!(words, vec![
+            TKey::new(), TKey::from_letter(1), TKey::from_letter(2),
+            TKey::from_letters(&[1, 1]), TKey::from_letters(&[1, 2])
+        ]);
+    }
+
+    #[test]
+    fn test_degree_norms_of_exp() {
+        type Tensor = DenseTensor<'static, Rational, 2, 4>;
+        type TKey = TensorKey<2>;
+
+        // The degree k component of exp(2 e_1) is 2^k / k! e_1^k
+        let x = Tensor::from_key_scalar(TKey::from_letter(1), Rational::from(2i8));
+        let sig = x.exp();
+
+        assert_eq!(sig.degree_norms(Norm::L1), vec![1.0, 2.0, 2.0, 4.0 / 3.0, 2.0 / 3.0]);
+        assert_eq!(sig.degree_norms(Norm::L2), sig.degree_norms(Norm::LInf));
+        assert_eq!(sig.inner_product(&x), Rational::from(4i8));
+        assert_eq!(x.l2_norm(), 2.0);
+    }
+
+    #[test]
+    fn test_degree_views() {
+        type Tensor = DenseTensor<'static, f64, 2, 3>;
+        type TKey = TensorKey<2>;
+
+        let x = Tensor::from_iterator(vec![(TKey::from_letter(1), 1.0), (TKey::from_letter(2), 2.0)]);
+        let mut sig = x.exp();
+
+        let level2 = sig.degree_view(2);
+        assert_eq!(level2.as_slice(), &[0.5, 1.0, 1.0, 2.0]);
+        assert_eq!(level2.get(TKey::from_letters(&[2, 1])), Some(&1.0));
+        assert_eq!(level2.get(TKey::from_letter(1)), None);
+        assert_eq!(level2.iter().map(|(k, _)| k).collect::<Vec<_>>(), vec![
+            TKey::from_letters(&[1, 1]), TKey::from_letters(&[1, 2]),
+            TKey::from_letters(&[2, 1]), TKey::from_letters(&[2, 2])
+        ]);
+        assert_eq!(level2.to_vector(), sig.project(2..=2).into());
+
+        let mut copied = sig.degree_view(1);
+        copied.as_mut_slice().fill(0.0);
+        assert_eq!(sig.get(TKey::from_letter(2)), Some(&2.0));
+
+        let mut level1 = sig.degree_view_mut(1);
+        for (_, v) in level1.iter_mut() {
+            *v *= 2.0;
+        }
+        *level1.get_mut(TKey::from_letter(1)).unwrap() = 0.0;
+        assert_eq!(sig.get(TKey::from_letter(2)), Some(&4.0));
+        assert_eq!(sig.get(TKey::from_letter(1)), Some(&0.0));
+
+        let mut truncated = x.exp();
+        truncated.truncate(1);
+        assert_eq!(truncated, Tensor::from_key(TKey::new()).add(&x));
+        assert_eq!(truncated.degree(), 1);
+        assert_eq!(x.exp().project(..=1), truncated);
+
+        let mut empty = Tensor::new();
+        empty.degree_view_mut(2).as_mut_slice()[0] = 1.0;
+        assert_eq!(empty, Tensor::from_key(TKey::from_letters(&[1, 1])));
+    }
+
+    #[test]
+    fn test_degree_bounds_above_vector_degree() {
+        type Tensor = DenseTensor<'static, f64, 2, 4>;
+        type TKey = TensorKey<2>;
+
+        let sig = Tensor::from_key(TKey::from_letter(1)).exp();
+
+        assert_eq!(sig.project(..=u32::MAX), sig);
+        assert_eq!(sig.project(70..), Tensor::new());
+        assert_eq!(sig.project(3..=u32::MAX), sig.project(3..));
+        assert_eq!(sig.degree_norm(70, Norm::L1), 0.0);
+
+        let mut truncated = sig.clone();
+        truncated.truncate(70);
+        assert_eq!(truncated, sig);
+    }
+
+    #[test]
+    fn test_wrap_external_buffers() {
+        use crate::vector::implementation::DenseVectorError;
+
+        type Wrapped<'a> = SimpleDenseVector<'a, TensorBasis<2>, f64>;
+
+        // unit, then (1), (2), then (1,1), (1,2), (2,1), (2,2)
+        let lhs_buf = vec![1.0, 1.0, 2.0, 0.0, 0.0, 0.0, 0.0];
+        let rhs_buf = vec![1.0, -1.0, 0.5, 0.0, 0.0, 0.0, 1.0];
+
+        let lhs = Wrapped::from_slice(&lhs_buf).unwrap();
+        let rhs = Wrapped::from_slice(&rhs_buf).unwrap();
+        let product = lhs.multiply(&rhs, Some(2));
+
+        let expected = Wrapped::from(lhs_buf.clone()).multiply(Wrapped::from(rhs_buf.clone()), Some(2));
+        assert_eq!(product, expected);
+        assert!(!lhs.is_owned() && !rhs.is_owned());
+
+        assert_eq!(Wrapped::from_slice(&lhs_buf[..5]).unwrap_err(), DenseVectorError::NotDegreeBoundary(5));
+        assert!(Wrapped::from_slice(&lhs_buf[..3]).is_ok());
+        assert!(Wrapped::from_slice(&[]).is_ok());
+
+        // Mutating a shared borrow copies, leaving the buffer alone.
+        let mut copied = Wrapped::from_slice(&lhs_buf).unwrap();
+        copied.scalar_rmultiply_inplace(2.0);
+        assert!(copied.is_owned());
+        assert_eq!(lhs_buf[1], 1.0);
+
+        // A mutable borrow is updated in place until the dimension has to grow.
+        let mut out_buf = lhs_buf.clone();
+        {
+            let mut out = Wrapped::from_mut_slice(&mut out_buf).unwrap();
+            out.multiply_inplace(&rhs, Some(2));
+            out.add_inplace(&lhs);
+            assert!(!out.is_owned());
+        }
+        assert_eq!(Wrapped::from(out_buf.clone()), expected.add(&lhs));
+
+        {
+            let mut out = Wrapped::from_mut_slice(&mut out_buf).unwrap();
+            out.insert_single(&TensorKey::from_letters(&[1, 1, 1]), 1.0);
+            assert!(out.is_owned());
+            out.insert_single(&TensorKey::new(), 0.0);
+        }
+        assert_eq!(out_buf[0], 2.0);
+    }
+
+    #[test]
+    fn test_float_kernels_match_exact_product() {
+        // Small integer coefficients, so that every float result is exact. The degree 3 and 4
+        // blocks are longer than a SIMD register and not a multiple of its width.
+        let lhs = SimpleDenseVector::<BasisT, Rational>::from_iterator(
+            BasisT::iter_keys().take(40).enumerate().map(|(i, k)| (k, Rational::from((i % 5) as i8 - 2)))
+        );
+        let rhs = SimpleDenseVector::<BasisT, Rational>::from_iterator(
+            BasisT::iter_keys().take(13).enumerate().map(|(i, k)| (k, Rational::from((i % 3) as i8 + 1)))
+        );
+
+        let mut exact = lhs.multiply(&rhs, Some(4));
+        exact.add_inplace(&lhs);
+        exact.mul_scal_rprod(&rhs, 2i8, Some(4));
+
+        let lhs64: SimpleDenseVector<BasisT, f64> = lhs.convert_coefficients();
+        let rhs64: SimpleDenseVector<BasisT, f64> = rhs.convert_coefficients();
+        let mut result64 = lhs64.multiply(&rhs64, Some(4));
+        result64.add_inplace(&lhs64);
+        result64.mul_scal_rprod(&rhs64, 2.0, Some(4));
+        assert_eq!(result64, exact.convert_coefficients());
+
+        let lhs32: SimpleDenseVector<BasisT, f32> = lhs.convert_coefficients();
+        let rhs32: SimpleDenseVector<BasisT, f32> = rhs.convert_coefficients();
+        let mut result32 = lhs32.multiply(&rhs32, Some(4));
+        result32.add_inplace(&lhs32);
+        result32.mul_scal_rprod(&rhs32, 2.0, Some(4));
+        assert_eq!(result32, exact.convert_coefficients());
+    }
+
+    #[test]
+    fn test_blocked_kernel_is_bit_identical() {
+        use tensor_mul_impl::{dense_tensor_multiply_tile, Tile, TILE_SIZE};
+
+        let lhs: Vec<f64> = (0..121).map(|i| (i as f64 * 0.7).sin()).collect();
+        let rhs: Vec<f64> = (0..121).map(|i| (i as f64 * 1.3).cos()).collect();
+        let mut func = |v: &f64| v / 3.0;
+
+        // The untiled scalar loop: scale each output block by the degree zero term of rhs, then
+        // add the products with the lower degrees of lhs, one degree at a time.
+        let mut expected = lhs.clone();
+        for out_deg in (0..=4).rev() {
+            let out_start = BasisT::start_of_degree(out_deg);
+            for index in BasisT::degree_range(out_deg) {
+                expected[index] = func(&(expected[index] * rhs[0]));
+            }
+            for lhs_d in (0..out_deg).rev() {
+                let lhs_start = BasisT::start_of_degree(lhs_d);
+                let rhs_start = BasisT::start_of_degree(out_deg - lhs_d);
+                let n = BasisT::degree_range(out_deg - lhs_d).len();
+                for i in 0..BasisT::degree_range(lhs_d).len() {
+                    for j in 0..n {
+                        let product = func(&(expected[lhs_start + i] * rhs[rhs_start + j]));
+                        expected[out_start + i * n + j] += product;
+                    }
+                }
+            }
+        }
+
+        for tile_size in [1, 2, 3, 8, 10, TILE_SIZE] {
+            let mut result = lhs.clone();
+            for out_deg in (0..=4).rev() {
+                let (lower, upper) = result.split_at_mut(BasisT::start_of_degree(out_deg));
+                let out = &mut upper[..BasisT::degree_range(out_deg).len()];
+                for (tile, out_tile) in Tile::<3>::split(out, out_deg, tile_size) {
+                    dense_tensor_multiply_tile(out_tile, &tile, lower, &rhs, (0..out_deg).rev(), Some(&rhs[..1]), Some(&mut func));
+                }
+            }
+            assert!(result.iter().zip(&expected).all(|(r, e)| r.to_bits() == e.to_bits()));
+        }
+    }
+
+}
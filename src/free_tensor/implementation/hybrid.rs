@@ -0,0 +1,155 @@
+use std::borrow::Borrow;
+use std::cmp;
+
+use crate::algebra::{Algebra, ProductFn};
+use crate::coefficients::CoefficientRing;
+use crate::DegreeType;
+
+use crate::vector::{ResizeableDenseVector, Vector, VectorWithDegree};
+use crate::vector::implementation::{HybridVector, OrderedSparseVector};
+
+use super::super::{TensorBasis, TensorKey};
+
+
+type HybridTensor<'a, S, const NLETTERS: DegreeType, const DENSE_DEGREE: DegreeType> =
+    HybridVector<'a, TensorBasis<NLETTERS>, S, DENSE_DEGREE>;
+
+
+// Products of two dense coefficients of total degree at most DENSE_DEGREE are computed block by
+// block as for dense tensors. All other products have degree above DENSE_DEGREE, so they are
+// collected and merged into the sparse part of the output in one pass.
+impl<'vec, 'a: 'vec, S, const NLETTERS: DegreeType, const DENSE_DEGREE: DegreeType> Algebra<'vec>
+    for HybridTensor<'a, S, NLETTERS, DENSE_DEGREE>
+        where S: 'static + CoefficientRing
+{
+    fn multiply_and_add_into_impl(
+        &mut self,
+        lhs: impl Borrow<Self>,
+        rhs: impl Borrow<Self>,
+        mut func: impl ProductFn<S>,
+        to_degree: Option<DegreeType>
+    )
+    {
+        let lhs_r = lhs.borrow();
+        let rhs_r = rhs.borrow();
+
+        let max_deg = cmp::min(
+            to_degree.expect("Max degree should be set for tensor types"),
+            lhs_r.degree() + rhs_r.degree()
+        );
+
+        let (lhs_dense, rhs_dense) = (lhs_r.dense_part().as_slice(), rhs_r.dense_part().as_slice());
+        let max_dense_deg = cmp::min(max_deg, DENSE_DEGREE);
+
+        if !lhs_dense.is_empty() && !rhs_dense.is_empty() {
+            let out = self.parts_mut().0;
+            let required_size = TensorBasis::<NLETTERS>::start_of_degree(max_dense_deg + 1);
+            if out.size() < required_size {
+                out.resize(required_size);
+            }
+
+            let out_data = out.as_mut_slice();
+            let mut buffer = Vec::new();
+            for out_deg in 0..=max_dense_deg {
+                for lhs_d in 0..=out_deg {
+                    let rhs_d = out_deg - lhs_d;
+                    let lhs_block = lhs_dense.get(TensorBasis::<NLETTERS>::degree_range(lhs_d));
+                    let rhs_block = rhs_dense.get(TensorBasis::<NLETTERS>::degree_range(rhs_d));
+
+                    if let (Some(lhs_block), Some(rhs_block)) = (lhs_block, rhs_block) {
+                        let out_block = &mut out_data[TensorBasis::<NLETTERS>::degree_range(out_deg)];
+                        S::outer_product_map_add_inplace(out_block, lhs_block, rhs_block, &mut func, &mut buffer);
+                    }
+                }
+            }
+        }
+
+        let mut products: Vec<(TensorKey<NLETTERS>, S)> = Vec::new();
+        for (lhs_k, lhs_v) in lhs_r.iter_degree_range(..=max_deg) {
+            let lhs_d = lhs_k.size();
+            // Products with lower degree rhs coefficients were computed densely above.
+            let rhs_min = if lhs_d <= DENSE_DEGREE { DENSE_DEGREE + 1 - lhs_d } else { 0 };
+
+            for (rhs_k, rhs_v) in rhs_r.iter_degree_range(rhs_min..=(max_deg - lhs_d)) {
+                products.push((lhs_k.concatenate(&rhs_k), func(&lhs_v.mul(rhs_v))));
+            }
+        }
+
+        if !products.is_empty() {
+            self.parts_mut().1.add_inplace(OrderedSparseVector::from_iterator(products));
+        }
+    }
+
+    fn multiply_into_impl(
+        &mut self,
+        rhs: impl Borrow<Self>,
+        func: impl ProductFn<S>,
+        to_degree: Option<DegreeType>
+    )
+    {
+        let mut result = Self::new();
+        result.multiply_and_add_into_impl(&*self, rhs, func, to_degree);
+        self.swap(result);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::basis::OrderedBasis;
+    use crate::free_tensor::DenseTensor;
+
+    type Dense = DenseTensor<'static, i64, 2, 4>;
+
+    fn sample() -> Vec<(TensorKey<2>, i64)>
+    {
+        (0..TensorBasis::<2>::start_of_degree(3))
+            .map(|i| (TensorBasis::<2>::index_to_key(i), (i as i64 * 7) % 5 - 2))
+            .collect()
+    }
+
+    fn check_product<const DENSE_DEGREE: DegreeType>()
+    {
+        let dense = Dense::from_iterator(sample());
+        let hybrid = HybridTensor::<i64, 2, DENSE_DEGREE>::from_iterator(sample());
+
+        let expected = dense.multiply(&dense, Some(4));
+        let result = hybrid.multiply(&hybrid, Some(4));
+
+        let result_as_dense = Dense::from_iterator(result.iter().map(|(k, v)| (k, *v)));
+        assert_eq!(result_as_dense, expected);
+        assert!(result.iter().all(|(k, _)| k.size() <= 4));
+
+        let mut inplace = hybrid.clone();
+        inplace.multiply_inplace(&hybrid, Some(4));
+        assert_eq!(inplace, result);
+    }
+
+    #[test]
+    fn test_product_matches_dense_for_each_split() {
+        check_product::<0>();
+        check_product::<1>();
+        check_product::<2>();
+        check_product::<4>();
+    }
+
+    #[test]
+    fn test_product_accumulates() {
+        let x = HybridTensor::<i64, 2, 1>::from_iterator(vec![
+            (TensorKey::from_letter(1), 1),
+            (TensorKey::from_letters(&[2, 2]), 1)
+        ]);
+
+        let mut result = x.clone();
+        result.add_mul(&x, &x, Some(3));
+
+        assert_eq!(result.get(TensorKey::from_letter(1)), Some(&1));
+        assert_eq!(result.get(TensorKey::from_letters(&[1, 1])), Some(&1));
+        assert_eq!(result.get(TensorKey::from_letters(&[2, 2])), Some(&1));
+        assert_eq!(result.get(TensorKey::from_letters(&[1, 2, 2])), Some(&1));
+        assert_eq!(result.get(TensorKey::from_letters(&[2, 2, 1])), Some(&1));
+        assert_eq!(result.get(TensorKey::from_letters(&[2, 2, 2, 2])), None);
+    }
+}
@@ -0,0 +1,571 @@
+use std::borrow::{Borrow, BorrowMut};
+use std::cmp;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use crate::algebra::{Algebra, MaybeSendSync, ProductFn};
+use crate::coefficients::{ApproxEq, CoefficientRing, CoefficientField, FromDegreeType, ToCoefficient};
+use crate::{DegreeType, DimensionType};
+
+use crate::vector::{DenseVector, ResizeableDenseVector, Vector, VectorApproxEq, VectorWithDegree};
+use crate::vector::SimpleDenseVector;
+use crate::vector::ops::{impl_algebra_ops, impl_vector_ops};
+
+use super::super::{TensorBasis, TensorKey, FreeTensor};
+use super::dense::tensor_mul_impl::{dense_tensor_multiply_tile, Tile, TILE_SIZE};
+
+
+type InnerVector<'a, S, const NLETTERS: DegreeType> = SimpleDenseVector<'a, TensorBasis<NLETTERS>, S>;
+
+
+/// Dense free tensor truncated at degree `DEPTH` that stores its coefficients as `S` but
+/// computes products in the higher precision type `A`.
+///
+/// Storing coefficients as `f32` halves the memory used by an `f64` tensor, but a plain `f32`
+/// tensor rounds after every term of every product, so the error grows with the number of terms
+/// contributing to each output coefficient. Here every output coefficient of a product is summed
+/// in `A` and rounded to `S` once. The exponential and logarithm also carry the division and the
+/// addition of the unit of each Horner step in `A`, so each step rounds once per coefficient.
+///
+/// Any pair of types that convert into each other with `ToCoefficient` can be used, for instance
+/// `f32` storage with `f64` accumulation, or `f64` storage with `DoubleDouble` accumulation.
+///
+/// The function passed to `Algebra::multiply_and_add_into_impl` and
+/// `Algebra::multiply_into_impl` acts on storage values, so it is applied to the rounded sum of
+/// the products contributing to each coefficient rather than to every product separately. This
+/// agrees with the other tensor types for the linear functions used by `Algebra`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MixedPrecisionTensor<'a, S, A, const NLETTERS: DegreeType, const DEPTH: DegreeType>
+    (SimpleDenseVector<'a, TensorBasis<NLETTERS>, S>, PhantomData<A>)
+        where S: CoefficientRing;
+
+
+impl<'a, S, A, const NLETTERS: DegreeType, const DEPTH: DegreeType> Deref
+    for MixedPrecisionTensor<'a, S, A, NLETTERS, DEPTH>
+        where S: CoefficientRing
+{
+    type Target = SimpleDenseVector<'a, TensorBasis<NLETTERS>, S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+
+impl<'a, S, A, const NLETTERS: DegreeType, const DEPTH: DegreeType> From<SimpleDenseVector<'a, TensorBasis<NLETTERS>, S>>
+    for MixedPrecisionTensor<'a, S, A, NLETTERS, DEPTH>
+        where S: CoefficientRing
+{
+    fn from(arg: SimpleDenseVector<'a, TensorBasis<NLETTERS>, S>) -> Self {
+        Self(arg, PhantomData)
+    }
+}
+
+
+impl<'vec, 'a: 'vec, S, A, const NLETTERS: DegreeType, const DEPTH: DegreeType> Vector<'vec>
+    for MixedPrecisionTensor<'a, S, A, NLETTERS, DEPTH>
+        where S: 'static + CoefficientRing,
+              A: 'static + PartialEq
+{
+    type BasisType = TensorBasis<NLETTERS>;
+    type KeyType = TensorKey<NLETTERS>;
+    type ScalarType = S;
+
+    fn new() -> Self
+    {
+        Self::from(InnerVector::<S, NLETTERS>::new())
+    }
+
+    fn from_key(key: impl Into<Self::KeyType>) -> Self {
+        Self::from(InnerVector::<S, NLETTERS>::from_key(key))
+    }
+
+    fn from_key_scalar(key: impl Into<Self::KeyType>, scalar: impl Into<Self::ScalarType>) -> Self {
+        Self::from(InnerVector::<S, NLETTERS>::from_key_scalar(key, scalar))
+    }
+
+    fn from_iterator(iterator: impl IntoIterator<Item=(Self::KeyType, Self::ScalarType)>) -> Self {
+        Self::from(InnerVector::<S, NLETTERS>::from_iterator(iterator))
+    }
+
+    fn swap(&mut self, mut other: impl BorrowMut<Self>) {
+        self.0.swap(&mut other.borrow_mut().0);
+    }
+
+    fn to_owned(&self) -> Self {
+        Self::from(Vector::to_owned(&self.0))
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    fn get(&self, key: impl Borrow<Self::KeyType>) -> Option<&Self::ScalarType> {
+        self.0.get(key)
+    }
+
+    fn get_mut(&mut self, key: impl Borrow<Self::KeyType>) -> Option<&mut Self::ScalarType> {
+        self.0.get_mut(key)
+    }
+
+    fn insert_single(&mut self, key: &Self::KeyType, value: impl Into<Self::ScalarType>) {
+        self.0.insert_single(key, value);
+    }
+
+    fn insert(&mut self, iterator: impl IntoIterator<Item=(Self::KeyType, Self::ScalarType)>) {
+        self.0.insert(iterator)
+    }
+
+    fn erase(&mut self, key: impl Borrow<Self::KeyType>) {
+        self.0.erase(key);
+    }
+
+    fn uminus_inplace(&mut self) -> &mut Self {
+        self.0.uminus_inplace();
+        self
+    }
+
+    fn add_inplace(&mut self, other: impl Borrow<Self>) -> &mut Self {
+        self.0.add_inplace(&other.borrow().0);
+        self
+    }
+
+    fn sub_inplace(&mut self, other: impl Borrow<Self>) -> &mut Self {
+        self.0.sub_inplace(&other.borrow().0);
+        self
+    }
+
+    fn scalar_lmultiply_inplace(&mut self, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        self.0.scalar_lmultiply_inplace(scalar);
+        self
+    }
+
+    fn scalar_rmultiply_inplace(&mut self, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        self.0.scalar_rmultiply_inplace(scalar);
+        self
+    }
+
+    fn add_scalar_rmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        self.0.add_scalar_rmultiply(&other.borrow().0, scalar);
+        self
+    }
+
+    fn add_scalar_lmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        self.0.add_scalar_lmultiply(&other.borrow().0, scalar);
+        self
+    }
+
+    fn scalar_rdivide_inplace_impl(&mut self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        self.0.scalar_rdivide_inplace_impl(rational);
+        self
+    }
+
+    fn add_scalar_rdivide_impl(&mut self, other: &Self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        self.0.add_scalar_rdivide_impl(&other.0, rational);
+        self
+    }
+
+    fn sub_scalar_rdivide_impl(&mut self, other: &Self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        self.0.sub_scalar_rdivide_impl(&other.0, rational);
+        self
+    }
+
+    fn add_linear_combination(&mut self, terms: &[(&Self, Self::ScalarType)]) -> &mut Self {
+        let inner: Vec<_> = terms.iter().map(|(other, scalar)| (&other.0, scalar.clone())).collect();
+        self.0.add_linear_combination(&inner);
+        self
+    }
+}
+
+impl<'vec, 'a: 'vec, S, A, const NLETTERS: DegreeType, const DEPTH: DegreeType> DenseVector<'vec>
+    for MixedPrecisionTensor<'a, S, A, NLETTERS, DEPTH>
+        where S: 'static + CoefficientRing,
+              A: 'static + PartialEq
+{
+    fn as_slice(&self) -> &[S] {
+        DenseVector::as_slice(&self.0)
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [S] {
+        DenseVector::as_mut_slice(&mut self.0)
+    }
+}
+
+// ResizeableDenseVector is deliberately not implemented, since that would give this type the
+// generic dense tensor multiplication, which accumulates in the storage type.
+impl<'vec, 'a: 'vec, S, A, const NLETTERS: DegreeType, const DEPTH: DegreeType> VectorWithDegree<'vec>
+    for MixedPrecisionTensor<'a, S, A, NLETTERS, DEPTH>
+        where S: 'static + CoefficientRing,
+              A: 'static + PartialEq
+{
+    fn degree(&self) -> DegreeType {
+        self.0.degree()
+    }
+}
+
+impl<'vec, 'a: 'vec, S, A, const NLETTERS: DegreeType, const DEPTH: DegreeType> VectorApproxEq<'vec>
+    for MixedPrecisionTensor<'a, S, A, NLETTERS, DEPTH>
+        where S: 'static + ApproxEq,
+              A: 'static + PartialEq
+{
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        self.0.approx_eq(&other.0, tol)
+    }
+}
+
+
+impl<'a, S, A, const NLETTERS: DegreeType, const DEPTH: DegreeType> MixedPrecisionTensor<'a, S, A, NLETTERS, DEPTH>
+    where S: 'static + CoefficientRing + ToCoefficient<A>,
+          A: 'static + CoefficientRing + ToCoefficient<S>
+{
+    /// Multiply self by `rhs` in place, truncating at `to_degree`, and set each coefficient to
+    /// `finish(index, sum)`, where `sum` is the coefficient of the product in the accumulator
+    /// type.
+    ///
+    /// Self may be empty, in which case every sum is zero and the result has the degree of rhs.
+    fn fused_multiply_inplace(
+        &mut self,
+        rhs: &Self,
+        to_degree: DegreeType,
+        mut finish: impl FnMut(DimensionType, A) -> S
+    )
+    {
+        let lhs_deg = self.0.degree();
+        let rhs_deg = rhs.0.degree();
+
+        let max_deg = cmp::min(to_degree, lhs_deg + rhs_deg);
+        let lhs_deg = cmp::min(lhs_deg, max_deg);
+        let rhs_deg = cmp::min(rhs_deg, max_deg);
+        // The products are formed from copies of the operands, so self can be overwritten in
+        // any order. The coefficients of self above the truncation degree are not needed.
+        let lhs_data = upcast::<S, A, NLETTERS>(self.0.as_slice(), lhs_deg);
+        let rhs_data = upcast::<S, A, NLETTERS>(rhs.0.as_slice(), rhs_deg);
+
+        self.0.resize(TensorBasis::<NLETTERS>::start_of_degree(max_deg + 1));
+        let data = self.0.as_mut_slice();
+
+        for_each_product_coefficient::<A, NLETTERS>(
+            &lhs_data, lhs_deg, &rhs_data, rhs_deg, max_deg,
+            |index, sum| data[index] = finish(index, sum)
+        );
+    }
+
+    /// Add the product of `lhs` and `rhs`, truncated at `to_degree`, to self, adding
+    /// `finish(index, sum)` to each coefficient, where `sum` is the coefficient of the product
+    /// in the accumulator type.
+    fn fused_multiply_and_add(
+        &mut self,
+        lhs: &Self,
+        rhs: &Self,
+        to_degree: DegreeType,
+        mut finish: impl FnMut(DimensionType, A) -> S
+    )
+    {
+        // The product with a zero vector is zero, so there is nothing to add.
+        if lhs.0.as_slice().is_empty() || rhs.0.as_slice().is_empty() {
+            return;
+        }
+
+        let lhs_deg = lhs.0.degree();
+        let rhs_deg = rhs.0.degree();
+
+        let max_deg = cmp::min(to_degree, lhs_deg + rhs_deg);
+        let lhs_deg = cmp::min(lhs_deg, max_deg);
+        let rhs_deg = cmp::min(rhs_deg, max_deg);
+        let lhs_data = upcast::<S, A, NLETTERS>(lhs.0.as_slice(), lhs_deg);
+        let rhs_data = upcast::<S, A, NLETTERS>(rhs.0.as_slice(), rhs_deg);
+
+        let required_size = TensorBasis::<NLETTERS>::start_of_degree(max_deg+1);
+        if self.0.as_slice().len() < required_size {
+            self.0.resize(required_size);
+        }
+        let data = self.0.as_mut_slice();
+
+        for_each_product_coefficient::<A, NLETTERS>(
+            &lhs_data, lhs_deg, &rhs_data, rhs_deg, max_deg,
+            |index, sum| { data[index].add_inplace(&finish(index, sum)); }
+        );
+    }
+}
+
+
+/// The coefficients of `data` up to degree `deg` in the accumulator type, padded with zeros to
+/// the end of that degree.
+fn upcast<S, A, const NLETTERS: DegreeType>(data: &[S], deg: DegreeType) -> Vec<A>
+    where S: ToCoefficient<A>,
+          A: CoefficientRing
+{
+    let len = TensorBasis::<NLETTERS>::start_of_degree(deg + 1);
+    let mut result: Vec<A> = data.iter().take(len).map(ToCoefficient::to_coefficient).collect();
+    result.resize(len, A::zero());
+    result
+}
+
+/// Compute the coefficients up to degree `max_deg` of the product of `lhs` and `rhs`, of
+/// degrees `lhs_deg` and `rhs_deg`, and pass each one to `each` with its index.
+///
+/// The operands must extend to the end of their degrees, as given by `upcast`. Each degree of the
+/// product is accumulated in a buffer by the cache-blocked dense kernel, so every coefficient
+/// is the sum of its products in `A`.
+fn for_each_product_coefficient<A, const NLETTERS: DegreeType>(
+    lhs: &[A],
+    lhs_deg: DegreeType,
+    rhs: &[A],
+    rhs_deg: DegreeType,
+    max_deg: DegreeType,
+    mut each: impl FnMut(DimensionType, A)
+)
+    where A: CoefficientRing
+{
+    let mut sums = Vec::new();
+    for out_deg in 0..=max_deg {
+        let out_deg_range = TensorBasis::<NLETTERS>::degree_range(out_deg);
+        sums.clear();
+        sums.resize(out_deg_range.len(), A::zero());
+
+        let lhs_deg_min = out_deg.saturating_sub(rhs_deg);
+        let lhs_degrees = (lhs_deg_min..=cmp::min(out_deg, lhs_deg)).rev();
+        for (tile, out_tile) in Tile::<NLETTERS>::split(&mut sums, out_deg, TILE_SIZE) {
+            dense_tensor_multiply_tile(out_tile, &tile, lhs, rhs, lhs_degrees.clone(), None,
+                None::<&mut fn(&A) -> A>);
+        }
+
+        for (index, sum) in out_deg_range.zip(sums.drain(..)) {
+            each(index, sum);
+        }
+    }
+}
+
+
+/// The function finishing each coefficient of a product for `Algebra`, which rounds the sum to
+/// the storage type and applies `func` to it.
+fn round_then<S, A>(mut func: impl FnMut(&S) -> S) -> impl FnMut(DimensionType, A) -> S
+    where A: ToCoefficient<S>
+{
+    move |_, sum| func(&sum.to_coefficient())
+}
+
+
+impl<'vec, 'a: 'vec, S, A, const NLETTERS: DegreeType, const DEPTH: DegreeType> Algebra<'vec>
+    for MixedPrecisionTensor<'a, S, A, NLETTERS, DEPTH>
+        where S: 'static + CoefficientRing + ToCoefficient<A>,
+              A: 'static + CoefficientRing + ToCoefficient<S>
+{
+    fn multiply_and_add_into_impl(
+        &mut self,
+        lhs: impl Borrow<Self>,
+        rhs: impl Borrow<Self>,
+        func: impl ProductFn<S>,
+        to_degree: Option<DegreeType>
+    )
+    {
+        self.fused_multiply_and_add(
+            lhs.borrow(),
+            rhs.borrow(),
+            to_degree.expect("Max degree should be set for tensor types"),
+            round_then(func)
+        );
+    }
+
+    fn multiply_into_impl(
+        &mut self,
+        rhs: impl Borrow<Self>,
+        func: impl ProductFn<S>,
+        to_degree: Option<DegreeType>
+    )
+    {
+        let rhs_r = rhs.borrow();
+
+        if self.as_slice().is_empty() {
+            return;
+        }
+        if rhs_r.as_slice().is_empty() {
+            self.clear();
+            return;
+        }
+
+        self.fused_multiply_inplace(
+            rhs_r,
+            to_degree.expect("Max degree should be set for tensor types"),
+            round_then(func)
+        );
+    }
+}
+
+
+impl<'vec, 'a: 'vec, S, A, const NLETTERS: DegreeType, const DEPTH: DegreeType> FreeTensor<'vec, S, NLETTERS>
+    for MixedPrecisionTensor<'a, S, A, NLETTERS, DEPTH>
+        where S: 'static + CoefficientField + ToCoefficient<A>,
+              S::RationalType: MaybeSendSync,
+              A: 'static + CoefficientField + ToCoefficient<S>
+{
+    const MAX_DEGREE: DegreeType = DEPTH;
+
+    fn exp(&self) -> Self
+    {
+        let mut result = Self::from_key(TensorKey::new());
+
+        // result = 1 + result * self / i, with the division and the unit added before rounding
+        for i in (1..=DEPTH).rev() {
+            let denom = A::RationalType::from_degree(&i);
+            result.fused_multiply_inplace(self, DEPTH, |index, mut sum| {
+                sum.div_inplace(&denom);
+                if index == 0 {
+                    sum.add_inplace(&A::one());
+                }
+                sum.to_coefficient()
+            });
+        }
+        result
+    }
+
+    fn log(&self) -> Self
+    {
+        let mut x = Vector::to_owned(self);
+        if let Some(unit) = x.get_mut(TensorKey::new()) {
+            *unit = S::zero();
+        }
+
+        let x_data: Vec<A> = x.as_slice().iter().map(ToCoefficient::to_coefficient).collect();
+        let mut rv = Self::new();
+
+        // The generic loop computes rv = (rv +- unit / i) * x. Since x has no unit term this is
+        // rv * x +- x / i, which can be formed without rounding in between.
+        for i in (1..=DEPTH).rev() {
+            let mut coeff = A::one().div(&A::RationalType::from_degree(&i));
+            if i % 2 == 0 {
+                coeff = coeff.uminus();
+            }
+
+            rv.fused_multiply_inplace(&x, DEPTH, |index, mut sum| {
+                if let Some(v) = x_data.get(index) {
+                    sum.add_inplace(&coeff.mul(v));
+                }
+                sum.to_coefficient()
+            });
+        }
+
+        rv
+    }
+}
+
+impl_vector_ops!(['a, S, A, const NLETTERS: DegreeType, const DEPTH: DegreeType]
+    MixedPrecisionTensor<'a, S, A, NLETTERS, DEPTH>, S,
+    [S: 'static + CoefficientRing, A: 'static + PartialEq]);
+
+impl_algebra_ops!(['a, S, A, const NLETTERS: DegreeType, const DEPTH: DegreeType]
+    MixedPrecisionTensor<'a, S, A, NLETTERS, DEPTH>, DEPTH,
+    [S: 'static + CoefficientRing + ToCoefficient<A>, A: 'static + CoefficientRing + ToCoefficient<S>]);
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::basis::OrderedBasis;
+    use crate::free_tensor::DenseTensor;
+    use crate::vector::MapCoefficients;
+
+    type MixedTensor = MixedPrecisionTensor<'static, f32, f64, 3, 5>;
+    type SingleTensor = DenseTensor<'static, f32, 3, 5>;
+    type DoubleTensor = DenseTensor<'static, f64, 3, 5>;
+
+    fn sample_f64() -> DoubleTensor
+    {
+        DoubleTensor::from_iterator((1..TensorBasis::<3>::start_of_degree(3)).map(|i| {
+            let val = ((i * 37 % 11) as f64 - 5.0) / 3.7;
+            (TensorBasis::<3>::index_to_key(i), val)
+        }))
+    }
+
+    fn max_error(approx: &[f32], exact: &[f64]) -> f64
+    {
+        // Trailing coefficients missing from either slice are zero
+        (0..cmp::max(approx.len(), exact.len()))
+            .map(|i| {
+                let a = approx.get(i).map_or(0.0, |v| *v as f64);
+                (a - exact.get(i).copied().unwrap_or(0.0)).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_multiply_matches_dense_tensor() {
+        let exact = sample_f64();
+        let single: SingleTensor = exact.convert_coefficients();
+        let mixed = MixedTensor::from(Vector::to_owned(&*single));
+
+        let expected = exact.multiply(&exact, Some(5));
+        let result = mixed.multiply(&mixed, Some(5));
+
+        assert!(max_error(result.as_slice(), expected.as_slice()) < 1e-4);
+    }
+
+    #[test]
+    fn test_multiply_inplace_matches_multiply() {
+        let single: SingleTensor = sample_f64().convert_coefficients();
+        let mixed = MixedTensor::from(Vector::to_owned(&*single));
+
+        let mut result = mixed.clone();
+        result.multiply_inplace(&mixed, Some(5));
+
+        assert_eq!(result, mixed.multiply(&mixed, Some(5)));
+    }
+
+    #[test]
+    fn test_multiply_inplace_below_degree_of_self() {
+        type Small = MixedPrecisionTensor<'static, f32, f64, 2, 4>;
+        let deg1 = Small::from_iterator(vec![(TensorKey::new(), 1.0), (TensorKey::from_letter(1), 2.0)]);
+        let mut deg2 = Small::from_iterator(vec![
+            (TensorKey::new(), 1.0), (TensorKey::from_letter(2), 2.0), (TensorKey::from_letters(&[1, 2]), 3.0)]);
+
+        deg2.multiply_into_impl(&deg1, |x| *x, Some(1));
+
+        assert_eq!(deg2.as_slice(), &[1.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_exp_more_accurate_than_f32() {
+        // The degree two terms cancel most of the square of the large degree one terms, so the
+        // degree two coefficients of the exponential are small sums of large products.
+        let letters = [100.3f32, -87.1, 63.7];
+        let single = SingleTensor::from_iterator((1..TensorBasis::<3>::start_of_degree(3)).map(|i| {
+            let val = if i < 4 {
+                letters[i - 1]
+            } else {
+                let (a, b) = ((i - 4) / 3, (i - 4) % 3);
+                (0.125 - letters[a] as f64 * letters[b] as f64 / 2.0) as f32
+            };
+            (TensorBasis::<3>::index_to_key(i), val)
+        }));
+        let mixed = MixedTensor::from(Vector::to_owned(&*single));
+        let rounded_input: DoubleTensor = single.convert_coefficients();
+
+        let expected = rounded_input.exp();
+        let degree_two = TensorBasis::<3>::degree_range(2);
+        let mixed_error = max_error(&mixed.exp().as_slice()[degree_two.clone()],
+            &expected.as_slice()[degree_two.clone()]);
+        let single_error = max_error(&single.exp().as_slice()[degree_two.clone()],
+            &expected.as_slice()[degree_two]);
+        assert!(mixed_error * 10.0 < single_error, "{} vs {}", mixed_error, single_error);
+    }
+
+    #[test]
+    fn test_log_inverts_exp() {
+        let exact = sample_f64();
+        let single: SingleTensor = exact.convert_coefficients();
+        let mixed = MixedTensor::from(Vector::to_owned(&*single));
+        let rounded_input: DoubleTensor = single.convert_coefficients();
+
+        let expected_log = rounded_input.exp().log();
+        let mixed_log = mixed.exp().log();
+        assert!(max_error(mixed_log.as_slice(), expected_log.as_slice()) < 1e-3);
+        assert!(max_error(mixed_log.as_slice(), rounded_input.as_slice()) < 1e-3);
+    }
+}
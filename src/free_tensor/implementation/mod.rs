@@ -1,5 +1,10 @@
 mod dense;
 mod default;
+mod hybrid;
+mod mixed_precision;
+#[cfg(all(feature = "parallel", not(feature = "deterministic")))]
+mod parallel;
 
 
-pub use dense::DenseTensor;
\ No newline at end of file
+pub use dense::DenseTensor;
+pub use mixed_precision::MixedPrecisionTensor;
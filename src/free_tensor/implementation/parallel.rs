@@ -0,0 +1,241 @@
+//! Multi-threaded dense tensor multiplication, enabled by the `parallel` feature.
+//!
+//! The output is split into the same tiles as in the serial cache-blocked kernel, and the tiles
+//! are computed on the rayon thread pool, each directly in its part of the output. When a
+//! product is added to another tensor, the tiles of all degrees are independent and are computed
+//! together. When a tensor is multiplied in place, each degree is computed from the lower
+//! degrees of the same tensor, so the degrees are taken one at a time from the top and the tiles
+//! of each degree are computed together. Either way, the tiles cover the large top degrees
+//! finely enough that every thread has work, even though those degrees hold nearly all of the
+//! output.
+//!
+//! The function applied to each product by `Algebra` is called from the pool, which is why it
+//! must be `Send` and `Sync` with this feature. Each coefficient receives the same products in
+//! the same order as in the serial kernel, so the results are identical to it.
+
+use std::cmp;
+use std::mem;
+
+use rayon::prelude::*;
+
+use crate::coefficients::CoefficientRing;
+use crate::free_tensor::TensorBasis;
+use crate::{DegreeType, DimensionType};
+
+use super::dense::tensor_mul_impl::{dense_tensor_multiply_tile, Tile, TILE_SIZE};
+
+
+/// Output dimension above which the dense `Algebra` implementation multiplies in parallel.
+/// Below this, the cost of distributing the work exceeds the cost of the product.
+pub(super) const MIN_PARALLEL_DIMENSION: DimensionType = 1 << 12;
+
+/// Add the product of the tensors with coefficients `lhs` and `rhs`, of degrees `lhs_deg` and
+/// `rhs_deg`, to `out`, which holds the coefficients up to the truncation degree. Each product
+/// is passed through `func` first, if it is given.
+pub(super) fn dense_tensor_multiply_and_add<S, F, const NLETTERS: DegreeType>(
+    out: &mut [S],
+    lhs: &[S],
+    lhs_deg: DegreeType,
+    rhs: &[S],
+    rhs_deg: DegreeType,
+    func: Option<&F>,
+)
+    where S: CoefficientRing + Send + Sync,
+          F: Fn(&S) -> S + Sync
+{
+    multiply_and_add_in_tiles::<S, F, NLETTERS>(out, lhs, lhs_deg, rhs, rhs_deg, func, TILE_SIZE);
+}
+
+/// Multiply `out`, a tensor of degree `lhs_deg`, by the tensor with coefficients `rhs` of
+/// degree `rhs_deg` in place, truncating at degree `max_deg`. The coefficients of `out` must
+/// already extend to `max_deg`.
+pub(super) fn dense_tensor_multiply_inplace<S, F, const NLETTERS: DegreeType>(
+    out: &mut [S],
+    lhs_deg: DegreeType,
+    rhs: &[S],
+    rhs_deg: DegreeType,
+    max_deg: DegreeType,
+    func: Option<&F>,
+)
+    where S: CoefficientRing + Send + Sync,
+          F: Fn(&S) -> S + Sync
+{
+    multiply_inplace_in_tiles::<S, F, NLETTERS>(out, lhs_deg, rhs, rhs_deg, max_deg, func, TILE_SIZE);
+}
+
+
+fn multiply_and_add_in_tiles<S, F, const NLETTERS: DegreeType>(
+    out: &mut [S],
+    lhs: &[S],
+    lhs_deg: DegreeType,
+    rhs: &[S],
+    rhs_deg: DegreeType,
+    func: Option<&F>,
+    tile_size: DimensionType,
+)
+    where S: CoefficientRing + Send + Sync,
+          F: Fn(&S) -> S + Sync
+{
+    // Each task computes one tile of the cache-blocked serial kernel.
+    let mut tasks = Vec::new();
+    let mut remaining = out;
+    let mut out_deg = 0;
+    while !remaining.is_empty() {
+        let (block, rest) = mem::take(&mut remaining)
+            .split_at_mut(TensorBasis::<NLETTERS>::degree_range(out_deg).len());
+        remaining = rest;
+        tasks.extend(Tile::<NLETTERS>::split(block, out_deg, share_of(block.len(), tile_size))
+            .map(|(tile, out)| (out_deg, tile, out)));
+        out_deg += 1;
+    }
+
+    tasks.into_par_iter().for_each(|(out_deg, tile, out)| {
+        let mut func = func;
+        let lhs_deg_min = out_deg.saturating_sub(rhs_deg);
+        let lhs_deg_max = cmp::min(out_deg, lhs_deg);
+
+        dense_tensor_multiply_tile(out, &tile, lhs, rhs, (lhs_deg_min..=lhs_deg_max).rev(), None,
+            func.as_mut());
+    });
+}
+
+fn multiply_inplace_in_tiles<S, F, const NLETTERS: DegreeType>(
+    out: &mut [S],
+    lhs_deg: DegreeType,
+    rhs: &[S],
+    rhs_deg: DegreeType,
+    max_deg: DegreeType,
+    func: Option<&F>,
+    tile_size: DimensionType,
+)
+    where S: CoefficientRing + Send + Sync,
+          F: Fn(&S) -> S + Sync
+{
+    // As in the serial kernel, the degrees are computed from the top, so that the lower degrees
+    // of out still hold their original values.
+    for out_deg in (0..=max_deg).rev() {
+        let out_deg_range = TensorBasis::<NLETTERS>::degree_range(out_deg);
+        let (lower, upper) = out.split_at_mut(out_deg_range.start);
+        let lower = &*lower;
+
+        let scale = if out_deg <= lhs_deg {
+            Some(&rhs[TensorBasis::<NLETTERS>::degree_range(0)])
+        } else {
+            None
+        };
+
+        let lhs_deg_min = out_deg.saturating_sub(rhs_deg);
+        let lhs_degrees = lhs_deg_min..cmp::min(out_deg, lhs_deg + 1);
+
+        let block = &mut upper[..out_deg_range.len()];
+        let tile_size = share_of(block.len(), tile_size);
+        let tiles: Vec<_> = Tile::<NLETTERS>::split(block, out_deg, tile_size).collect();
+        tiles.into_par_iter().for_each(|(tile, out)| {
+            let mut func = func;
+            dense_tensor_multiply_tile(out, &tile, lower, rhs, lhs_degrees.clone().rev(), scale,
+                func.as_mut());
+        });
+    }
+}
+
+// Tiles of a block no larger than an even share of it for each thread of the pool, so that
+// every thread has work even when the block would fit in a single tile.
+fn share_of(block_len: DimensionType, tile_size: DimensionType) -> DimensionType
+{
+    cmp::min(tile_size, cmp::max(1, block_len / rayon::current_num_threads()))
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::algebra::Algebra;
+    use crate::coefficients::Rational;
+    use crate::free_tensor::DenseTensor;
+    use crate::basis::OrderedBasis;
+    use crate::vector::{DenseVector, MapCoefficients, Vector, VectorWithDegree};
+
+    type Tensor = DenseTensor<'static, Rational, 3, 5>;
+
+    fn sample(modulus: usize, len: usize) -> Tensor
+    {
+        Tensor::from_iterator(TensorBasis::<3>::iter_keys()
+            .take(len)
+            .enumerate()
+            .map(|(i, k)| (k, Rational::from((i % modulus) as i8 - 2))))
+    }
+
+    #[test]
+    fn test_tiled_product_matches_serial() {
+        let lhs = sample(5, 121);
+        let rhs = sample(4, 40);
+        let expected = lhs.multiply(&rhs, Some(5));
+        let mut expected_scaled = lhs.clone();
+        expected_scaled.mul_scal_rprod(&rhs, 3i8, Some(5));
+
+        let unmapped = None::<&fn(&Rational) -> Rational>;
+        let three = Rational::from(3i8);
+        let scale = |v: &Rational| v.mul(&three);
+
+        // Small tiles, so that both ways of splitting a block are used.
+        for tile_size in [1, 3, 4, 10, TILE_SIZE] {
+            let mut product = vec![Rational::zero(); expected.as_slice().len()];
+            multiply_and_add_in_tiles::<_, _, 3>(
+                &mut product, lhs.as_slice(), lhs.degree(), rhs.as_slice(), rhs.degree(), unmapped, tile_size);
+            assert_eq!(product.as_slice(), expected.as_slice());
+
+            let mut product = lhs.as_slice().to_vec();
+            product.resize(expected.as_slice().len(), Rational::zero());
+            multiply_inplace_in_tiles::<_, _, 3>(
+                &mut product, lhs.degree(), rhs.as_slice(), rhs.degree(), 5, Some(&scale), tile_size);
+            assert_eq!(product.as_slice(), expected_scaled.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_large_products_run_on_pool() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        // Dimension 21845 is above the threshold for multiplying in parallel, and the top
+        // degree is split into several tiles even with a single thread.
+        type Large = DenseTensor<'static, f64, 4, 7>;
+        type LargeExact = DenseTensor<'static, Rational, 4, 7>;
+
+        let lhs = LargeExact::from_iterator(TensorBasis::<4>::iter_keys()
+            .take(341)
+            .enumerate()
+            .map(|(i, k)| (k, Rational::from((i % 3) as i8 - 1))));
+        let rhs = LargeExact::from_iterator(TensorBasis::<4>::iter_keys()
+            .take(85)
+            .enumerate()
+            .map(|(i, k)| (k, Rational::from((i % 5) as i8 - 2))));
+
+        let mut expected = lhs.multiply(&rhs, Some(7));
+        expected.mul_scal_lprod(&rhs, 3i8, Some(7));
+        expected.add_mul(&lhs, &rhs, Some(7));
+
+        // Called from outside the pool, the function only sees a thread index if the product
+        // was handed to the pool.
+        let on_pool = AtomicBool::new(false);
+        let triple = |v: &f64| {
+            if rayon::current_thread_index().is_some() {
+                on_pool.store(true, Ordering::Relaxed);
+            }
+            3.0 * v
+        };
+
+        let lhs: Large = lhs.convert_coefficients();
+        let rhs: Large = rhs.convert_coefficients();
+        let mut result = lhs.multiply(&rhs, Some(7));
+        assert!(rayon::current_thread_index().is_none());
+        result.multiply_into_impl(&rhs, &triple, Some(7));
+        assert!(on_pool.swap(false, Ordering::Relaxed));
+        result.multiply_and_add_into_impl(&lhs, &rhs, |v| triple(v) / 3.0, Some(7));
+        assert!(on_pool.load(Ordering::Relaxed));
+
+        assert!(result.as_slice().len() >= MIN_PARALLEL_DIMENSION);
+        assert_eq!(result, expected.convert_coefficients());
+    }
+}
@@ -7,7 +7,7 @@ use std::borrow::{Borrow, BorrowMut};
 
 
 use crate::DegreeType;
-use crate::algebra::Algebra;
+use crate::algebra::{Algebra, MaybeSendSync};
 use crate::coefficients::{CoefficientField, FromDegreeType};
 
 use crate::vector::{Vector};
@@ -15,11 +15,13 @@ use crate::vector::{Vector};
 
 
 pub use tensor_basis::{TensorKey, TensorBasis, TensorKeyIterator};
-pub use implementation::DenseTensor;
+pub use implementation::{DenseTensor, MixedPrecisionTensor};
 
 
 pub trait FreeTensor<'vec, S: CoefficientField, const NLETTERS: DegreeType>
-    : Algebra<'vec, BasisType=TensorBasis<NLETTERS>, KeyType=TensorKey<NLETTERS>, ScalarType=S, RationalType=S::RationalType> {
+    : Algebra<'vec, BasisType=TensorBasis<NLETTERS>, KeyType=TensorKey<NLETTERS>, ScalarType=S>
+    where S::RationalType: MaybeSendSync
+{
 
     const MAX_DEGREE: DegreeType;
 
@@ -29,8 +31,8 @@ pub trait FreeTensor<'vec, S: CoefficientField, const NLETTERS: DegreeType>
         let mut result = Self::from_key(Self::KeyType::new());
         let borrowed_result = result.borrow_mut();
 
-        for i in (1..Self::MAX_DEGREE).rev() {
-            borrowed_result.mul_rat_rdiv(self, Self::ScalarType::from_degree(&i), Some(Self::MAX_DEGREE));
+        for i in (1..=Self::MAX_DEGREE).rev() {
+            borrowed_result.mul_rat_rdiv(self, S::RationalType::from_degree(&i), Some(Self::MAX_DEGREE));
             borrowed_result.add_inplace(&tunit);
         }
         result
@@ -44,11 +46,11 @@ pub trait FreeTensor<'vec, S: CoefficientField, const NLETTERS: DegreeType>
         let oself = old_self.borrow();
 
         if let Some(unit) = x.get_mut(Self::KeyType::new()) {
-            *unit = Self::ScalarType::ZERO.clone();
+            *unit = Self::ScalarType::zero();
         }
 
-        for i in (1..Self::MAX_DEGREE).rev() {
-            self.mul_rat_rdiv(&x, Self::ScalarType::from_degree(&i), Some(Self::MAX_DEGREE -i + 1));
+        for i in (1..=Self::MAX_DEGREE).rev() {
+            self.mul_rat_rdiv(&x, S::RationalType::from_degree(&i), Some(Self::MAX_DEGREE -i + 1));
             self.add_inplace(oself);
         }
 
@@ -64,16 +66,16 @@ pub trait FreeTensor<'vec, S: CoefficientField, const NLETTERS: DegreeType>
         let mut rv = Self::new();
 
         if let Some(unit) = x.get_mut(kunit) {
-            *unit = Self::ScalarType::ZERO.clone();
+            *unit = Self::ScalarType::zero();
         }
 
-        for i in (1..Self::MAX_DEGREE).rev() {
+        for i in (1..=Self::MAX_DEGREE).rev() {
             if i % 2 == 0 {
-                rv.sub_scalar_rdivide(&tunit, Self::RationalType::from_degree(&i));
+                rv.sub_scalar_rdivide(&tunit, S::RationalType::from_degree(&i));
             } else {
-                rv.add_scalar_rdivide(&tunit, Self::RationalType::from_degree(&i));
+                rv.add_scalar_rdivide(&tunit, S::RationalType::from_degree(&i));
             }
-            rv.multiply(&x, Some(Self::MAX_DEGREE));
+            rv.multiply_inplace(&x, Some(Self::MAX_DEGREE));
         }
 
         rv
@@ -26,12 +26,14 @@ impl<const NLETTERS: DegreeType> TensorBasis<NLETTERS> {
         TensorKey::<NLETTERS>::max_depth()
     }
 
-    pub(crate) const fn start_of_degree(deg: DegreeType) -> DimensionType
+    /// Index of the first key of degree `deg`.
+    pub const fn start_of_degree(deg: DegreeType) -> DimensionType
     {
         (((NLETTERS as DimensionType).pow(deg) - 1) / (NLETTERS as DimensionType - 1)) as DimensionType
     }
 
-    pub(crate) const fn degree_range(deg: DegreeType) -> Range<DimensionType>
+    /// Range of the indices of the keys of degree `deg`.
+    pub const fn degree_range(deg: DegreeType) -> Range<DimensionType>
     {
         Self::start_of_degree(deg)..Self::start_of_degree(deg+1)
     }
@@ -68,8 +70,11 @@ impl<const NLETTERS: DegreeType> Basis for TensorBasis<NLETTERS> {
 impl<const NLETTERS: DegreeType> OrderedBasis for TensorBasis<NLETTERS> {
     type KeyIterator = TensorBasisIterator<NLETTERS>;
 
+    // Words are ordered by length and then lexicographically, which is the order of their
+    // indices. The packed data alone does not distinguish words of different length that
+    // start with the first letter.
     fn compare(lhs: &Self::KeyType, rhs: &Self::KeyType) -> Ordering {
-        Ord::cmp(&lhs.data(), &rhs.data())
+        Ord::cmp(&(lhs.size(), lhs.data()), &(rhs.size(), rhs.data()))
     }
 
     fn iter_keys() -> Self::KeyIterator {
@@ -226,6 +231,17 @@ mod tests {
         assert_eq!(Basis::compare(&key2, &key1), Ordering::Greater);
     }
 
+    #[test]
+    fn test_compare_distinguishes_lengths() {
+        let key1 = Key::new();
+        let key2 = Key::from_letter(1);
+        let key3 = Key::from_letters(&[1, 1]);
+
+        assert_eq!(Basis::compare(&key1, &key2), Ordering::Less);
+        assert_eq!(Basis::compare(&key2, &key3), Ordering::Less);
+        assert_eq!(Basis::compare(&Key::from_letter(3), &key3), Ordering::Less);
+    }
+
     #[test]
     fn test_equal_keys_equal() {
         let key1 = Key::from_letters(&[1, 2, 3]);
@@ -4,6 +4,7 @@ use std::fmt::{self, Debug, Display, Formatter};
 
 use crate::{DegreeType, DimensionType, LetterType};
 use crate::implementation::{const_logn, make_mask};
+use crate::vector::VectorIteratorItem;
 
 
 /// Compact representation of a tensor word.
@@ -22,7 +23,7 @@ use crate::implementation::{const_logn, make_mask};
 /// width 10 (1024 letters), we can still pack approximately 6 letters into a 64 bit integer type.
 /// The only minor bump is that we must subtract 1 from the letter so the stored word consists of
 /// letters from the modified alphabet 0 to N-1.
-#[derive(PartialEq, Clone)]
+#[derive(PartialEq, Eq, Hash, Clone)]
 pub struct TensorKey<const NLetters: u32>
 {
     size: DegreeType,
@@ -32,12 +33,14 @@ pub struct TensorKey<const NLetters: u32>
 
 impl<const NLETTERS: u32> Debug for TensorKey<NLETTERS> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Letters are stored with the first letter of the word in the highest position, so
+        // print them in the order given by the letter iterator.
         write!(f, "(")?;
-        for i in 0..self.size() {
+        for (i, letter) in TensorKeyIterator::new(self).enumerate() {
             if i > 0 {
-                write!(f, ", {}", self.get_letter(i))?;
+                write!(f, ", {}", letter)?;
             } else {
-                write!(f, "{}", self.get_letter(i))?;
+                write!(f, "{}", letter)?;
             }
         }
         write!(f, ")")
@@ -47,7 +50,7 @@ impl<const NLETTERS: u32> Debug for TensorKey<NLETTERS> {
 impl<const NLETTERS: u32> Display for TensorKey<NLETTERS> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         // In this case, the displayed key should be the same as the debug output
-        Display::fmt(self, f)
+        Debug::fmt(self, f)
     }
 }
 
@@ -203,6 +206,23 @@ impl<const NLETTERS: DegreeType> AsRef<TensorKey<NLETTERS>> for TensorKey<NLETTE
 }
 
 
+// Dense tensors compute their keys from indices, so their iterators yield keys by value.
+impl<'vec, S: 'vec, const NLETTERS: DegreeType> VectorIteratorItem<'vec, TensorKey<NLETTERS>, S>
+    for (TensorKey<NLETTERS>, &'vec S)
+{
+    type KeyItem = TensorKey<NLETTERS>;
+    type ValueItem = &'vec S;
+
+    fn key(&self) -> Self::KeyItem {
+        self.0.clone()
+    }
+
+    fn value(&self) -> Self::ValueItem {
+        self.1
+    }
+}
+
+
 
 pub struct TensorKeyIterator<'a, const NLETTERS: DegreeType>
 {
@@ -361,6 +381,14 @@ mod tests {
 
 
 
+    #[test]
+    fn test_display_matches_debug() {
+        let key = Key::from_letters(&[1, 2, 3]);
+
+        assert_eq!(format!("{}", key), "(1, 2, 3)");
+        assert_eq!(format!("{}", Key::new()), format!("{:?}", Key::new()));
+    }
+
     #[test]
     fn test_iterator_3_letters() {
         let key = Key::from_letters(&[1, 2, 3]);
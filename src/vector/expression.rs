@@ -0,0 +1,256 @@
+//! Lazy linear combinations of vectors.
+//!
+//! The arithmetic operators on vectors evaluate eagerly, so `&x * a + &y * b - &z / c` builds a
+//! temporary vector for every operation. A `LinearCombination` instead records references to
+//! the vectors together with their coefficients, and nothing is computed until it is evaluated.
+//! The whole combination is then added in one call to `Vector::add_linear_combination`, which
+//! dense vectors implement as a single pass over their storage.
+//!
+//! Combinations are started with `lazy`, and support the same operators as vectors:
+//!
+//! ```text
+//! let result = (lazy(&x) * a + lazy(&y) * b - lazy(&z) / c).evaluate();
+//! ```
+//!
+//! As for vectors, scalars multiply on the right. Combinations of many vectors can be collected
+//! from an iterator, since `LinearCombination` implements `Sum`.
+
+use std::borrow::BorrowMut;
+use std::iter::Sum;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::coefficients::{CoefficientField, CoefficientRing};
+use crate::vector::Vector;
+
+
+/// A sum of vectors, each multiplied on the right by a scalar, that has not been evaluated.
+#[derive(Debug, Clone)]
+pub struct LinearCombination<'a, V, S> {
+    terms: Vec<(&'a V, S)>
+}
+
+
+/// Start a linear combination consisting of `vector` alone.
+pub fn lazy<'vec, 'a, V, S>(vector: &'a V) -> LinearCombination<'a, V, S>
+    where V: Vector<'vec, ScalarType=S>,
+          S: CoefficientRing
+{
+    LinearCombination::from_term(vector, S::one())
+}
+
+
+impl<'a, V, S> LinearCombination<'a, V, S> {
+
+    /// The empty combination, which evaluates to zero.
+    pub fn new() -> Self
+    {
+        Self { terms: Vec::new() }
+    }
+
+    /// The combination consisting of `vector * scalar`.
+    pub fn from_term(vector: &'a V, scalar: S) -> Self
+    {
+        Self { terms: vec![(vector, scalar)] }
+    }
+
+    /// Add `vector * scalar` to the combination.
+    pub fn push(&mut self, vector: &'a V, scalar: S)
+    {
+        self.terms.push((vector, scalar));
+    }
+
+    /// The vectors of the combination and their coefficients.
+    pub fn terms(&self) -> &[(&'a V, S)]
+    {
+        &self.terms
+    }
+
+    /// Add the value of the combination to `out`.
+    pub fn add_to<'vec>(&self, mut out: impl BorrowMut<V>)
+        where V: Vector<'vec, ScalarType=S>
+    {
+        out.borrow_mut().add_linear_combination(&self.terms);
+    }
+
+    /// Compute the value of the combination as a new vector.
+    pub fn evaluate<'vec>(&self) -> V
+        where V: Vector<'vec, ScalarType=S>
+    {
+        let mut result = V::new();
+        result.add_linear_combination(&self.terms);
+        result
+    }
+}
+
+impl<'a, V, S> Default for LinearCombination<'a, V, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+impl<'a, V, S, Rhs> Add<Rhs> for LinearCombination<'a, V, S>
+    where Rhs: Into<LinearCombination<'a, V, S>>
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: Rhs) -> Self {
+        self.terms.extend(rhs.into().terms);
+        self
+    }
+}
+
+impl<'a, V, S, Rhs> Sub<Rhs> for LinearCombination<'a, V, S>
+    where Rhs: Into<LinearCombination<'a, V, S>>,
+          S: CoefficientRing
+{
+    type Output = Self;
+
+    fn sub(mut self, rhs: Rhs) -> Self {
+        self.terms.extend(rhs.into().terms.into_iter().map(|(v, s)| (v, s.uminus())));
+        self
+    }
+}
+
+impl<'a, V, S: CoefficientRing> Neg for LinearCombination<'a, V, S> {
+    type Output = Self;
+
+    fn neg(mut self) -> Self {
+        self.terms.iter_mut().for_each(|(_, s)| { *s = s.uminus(); });
+        self
+    }
+}
+
+impl<'a, V, S: CoefficientRing> Mul<S> for LinearCombination<'a, V, S> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: S) -> Self {
+        self.terms.iter_mut().for_each(|(_, s)| { s.mul_inplace(&rhs); });
+        self
+    }
+}
+
+impl<'a, V, S, Q> Div<Q> for LinearCombination<'a, V, S>
+    where S: CoefficientField,
+          Q: Into<S::RationalType>
+{
+    type Output = Self;
+
+    fn div(mut self, rhs: Q) -> Self {
+        let rational = rhs.into();
+        self.terms.iter_mut().for_each(|(_, s)| { s.div_inplace(&rational); });
+        self
+    }
+}
+
+impl<'vec, 'a, V, S> From<&'a V> for LinearCombination<'a, V, S>
+    where V: Vector<'vec, ScalarType=S>,
+          S: CoefficientRing
+{
+    fn from(vector: &'a V) -> Self {
+        lazy(vector)
+    }
+}
+
+impl<'a, V, S> Sum for LinearCombination<'a, V, S> {
+    fn sum<I: Iterator<Item=Self>>(iter: I) -> Self {
+        Self { terms: iter.flat_map(|c| c.terms).collect() }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::coefficients::Rational;
+    use crate::free_tensor::{DenseTensor, TensorBasis, TensorKey};
+    use crate::vector::implementation::{OrderedSparseVector, SimpleSparseVector};
+    use crate::vector::SimpleDenseVector;
+    use crate::basis::OrderedBasis;
+
+    type TKey = TensorKey<2>;
+    type Vect<'a> = SimpleDenseVector<'a, TensorBasis<2>, f64>;
+
+    fn sample(seed: f64, len: usize) -> Vect<'static>
+    {
+        Vect::from((0..len).map(|i| (i as f64 * seed).sin()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_matches_eager_operators() {
+        let x = Vect::from_iterator(vec![(TKey::new(), 1.0), (TKey::from_letter(1), 2.0)]);
+        let y = Vect::from_key(TKey::from_letter(2));
+        let z = Vect::from_iterator(vec![(TKey::from_letter(1), 4.0), (TKey::from_letters(&[1, 2]), 8.0)]);
+
+        let expected = &x * 3.0 + &y * 0.5 - &z / 4.0;
+        let result = (lazy(&x) * 3.0 + lazy(&y) * 0.5 - lazy(&z) / 4.0).evaluate();
+
+        assert_eq!(result, expected);
+        assert_eq!((-(lazy(&x) - &y)).evaluate(), &y - &x);
+
+        // Division divides the coefficients of the terms, rather than multiplying them by a
+        // rounded inverse.
+        let combination = (lazy(&x) * 5.0 - lazy(&y) * 7.0) / 3.0;
+        assert_eq!(combination.terms()[0].1, 5.0 / 3.0);
+        assert_eq!(combination.terms()[1].1, -7.0 / 3.0);
+    }
+
+    #[test]
+    fn test_dense_combination_is_one_pass_of_fused_updates() {
+        // Longer than a block, with vectors of different lengths.
+        let vectors: Vec<Vect> = (1..=12).map(|i| sample(0.1 * i as f64, 100 * i)).collect();
+        let scalars: Vec<f64> = (1..=12).map(|i| 1.0 / i as f64).collect();
+
+        let mut expected = sample(0.05, 300);
+        for (v, s) in vectors.iter().zip(&scalars) {
+            expected.add_scalar_rmultiply(v, *s);
+        }
+
+        let mut result = sample(0.05, 300);
+        let combination: LinearCombination<_, _> = vectors.iter()
+            .zip(&scalars)
+            .map(|(v, s)| lazy(v) * *s)
+            .sum();
+        combination.add_to(&mut result);
+
+        assert_eq!(result.as_slice().len(), 1200);
+        assert!(result.as_slice().iter().zip(expected.as_slice()).all(|(r, e)| r.to_bits() == e.to_bits()));
+    }
+
+    #[test]
+    fn test_combination_of_tensors() {
+        type Tensor = DenseTensor<'static, f64, 2, 3>;
+        let x = Tensor::from_iterator(TensorBasis::<2>::iter_keys().take(15).map(|k| (k, 1.0)));
+        let y = Tensor::from_key(TKey::from_letters(&[2, 2]));
+
+        let result = (lazy(&x) * 2.0 - &y).evaluate();
+
+        assert_eq!(result.get(TKey::from_letters(&[2, 2])), Some(&1.0));
+        assert_eq!(result.get(TKey::from_letters(&[1, 2, 1])), Some(&2.0));
+    }
+
+    #[test]
+    fn test_sparse_combinations() {
+        type Sparse = SimpleSparseVector<'static, TensorBasis<2>, Rational, TKey>;
+        type Ordered = OrderedSparseVector<'static, TensorBasis<2>, Rational, TKey>;
+
+        let x = Sparse::from_key(TKey::from_letter(1));
+        let y = Sparse::from_iterator(vec![(TKey::from_letter(1), Rational::from(1i8)), (TKey::from_letter(2), Rational::from(1i8))]);
+
+        let result = (lazy(&x) * Rational::from(2i8) - lazy(&y) * Rational::from(2i8) + lazy(&y) / 2i8).evaluate();
+        assert_eq!(result.get(TKey::from_letter(1)), Some(&Rational::new(1, 2)));
+        assert_eq!(result.get(TKey::from_letter(2)), Some(&Rational::new(-3, 2)));
+
+        let x = Ordered::from_key(TKey::from_letter(1));
+        let y = Ordered::from_key(TKey::from_letter(2));
+        let mut acc = Ordered::from_key(TKey::new());
+        acc.sub_scalar_rdivide(&x, 3i8);
+        (lazy(&x) / 3i8 + &y).add_to(&mut acc);
+
+        assert_eq!(acc.get(TKey::from_letter(1)), None);
+        assert_eq!(acc.get(TKey::from_letter(2)), Some(&Rational::from(1i8)));
+        assert_eq!(acc.get(TKey::new()), Some(&Rational::from(1i8)));
+    }
+}
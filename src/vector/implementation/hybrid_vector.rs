@@ -0,0 +1,441 @@
+use std::borrow::{Borrow, BorrowMut};
+use std::cmp;
+use std::ops::RangeBounds;
+
+use crate::basis::OrderedBasisWithDegree;
+use crate::coefficients::{ApproxEq, CoefficientField, CoefficientRing};
+use crate::{DegreeType, DimensionType};
+use crate::vector::{DegreeProjection, MapCoefficients, ResizeableDenseVector, Vector, VectorApproxEq, VectorWithDegree};
+
+use super::{OrderedSparseVector, SimpleDenseVector};
+
+
+/// Vector over a graded basis storing low degrees densely and high degrees sparsely.
+///
+/// Coefficients of keys with degree at most `DENSE_DEGREE` are stored in a `SimpleDenseVector`,
+/// and all others in an `OrderedSparseVector`. The low degrees of a graded basis are small and
+/// usually filled, so dense storage is both compact and fast there, whereas the number of keys
+/// grows exponentially with the degree, so only the non-zero coefficients of the high degrees
+/// are stored.
+#[derive(Debug)]
+pub struct HybridVector<'a, B, S, const DENSE_DEGREE: DegreeType>(
+    SimpleDenseVector<'a, B, S>,
+    OrderedSparseVector<'a, B, S, B::KeyType>
+)
+    where B: OrderedBasisWithDegree,
+          B::KeyType: Clone,
+          S: CoefficientRing;
+
+
+impl<'a, B, S, const DENSE_DEGREE: DegreeType> HybridVector<'a, B, S, DENSE_DEGREE>
+    where B: OrderedBasisWithDegree,
+          B::KeyType: Clone,
+          S: CoefficientRing
+{
+    pub fn from_parts(
+        dense: SimpleDenseVector<'a, B, S>,
+        sparse: OrderedSparseVector<'a, B, S, B::KeyType>
+    ) -> Self
+    {
+        assert!(dense.size() <= Self::dense_dimension(),
+                "dense part has coefficients above degree {}", DENSE_DEGREE);
+        assert!(sparse.iter_degree_range(..=DENSE_DEGREE).next().is_none(),
+                "sparse part has coefficients of degree at most {}", DENSE_DEGREE);
+        Self(dense, sparse)
+    }
+
+    /// Dimension of the dense part when it holds all of its degrees.
+    pub fn dense_dimension() -> DimensionType
+    {
+        B::start_of_degree(DENSE_DEGREE + 1)
+    }
+
+    pub fn dense_part(&self) -> &SimpleDenseVector<'a, B, S>
+    {
+        &self.0
+    }
+
+    pub fn sparse_part(&self) -> &OrderedSparseVector<'a, B, S, B::KeyType>
+    {
+        &self.1
+    }
+
+    pub fn into_parts(self) -> (SimpleDenseVector<'a, B, S>, OrderedSparseVector<'a, B, S, B::KeyType>)
+    {
+        (self.0, self.1)
+    }
+
+    pub(crate) fn parts_mut(&mut self)
+        -> (&mut SimpleDenseVector<'a, B, S>, &mut OrderedSparseVector<'a, B, S, B::KeyType>)
+    {
+        (&mut self.0, &mut self.1)
+    }
+
+    fn is_dense_key(key: &B::KeyType) -> bool
+    {
+        B::degree(key) <= DENSE_DEGREE
+    }
+
+    /// Iterator over the non-zero coefficients whose keys have degree in `degrees`, in basis
+    /// order.
+    pub fn iter_degree_range<'s>(&'s self, degrees: impl RangeBounds<DegreeType> + Clone)
+        -> impl Iterator<Item=(B::KeyType, &'s S)> + 's
+    {
+        let dense = self.0.as_slice();
+        let dense_range = B::degree_index_range(degrees.clone(), dense.len());
+        let zero = S::zero();
+
+        dense[dense_range.clone()].iter()
+            .zip(dense_range)
+            .filter(move |(v, _)| **v != zero)
+            .map(|(v, i)| (B::index_to_key(i), v))
+            .chain(self.1.iter_degree_range(degrees).map(|(k, v)| (k.clone(), v)))
+    }
+
+    /// Iterator over all the non-zero coefficients in basis order.
+    pub fn iter(&self) -> impl Iterator<Item=(B::KeyType, &S)> + '_
+    {
+        self.iter_degree_range(..)
+    }
+}
+
+
+impl<'a, B, S, const DENSE_DEGREE: DegreeType> Clone for HybridVector<'a, B, S, DENSE_DEGREE>
+    where B: OrderedBasisWithDegree,
+          B::KeyType: Clone,
+          S: CoefficientRing
+{
+    fn clone(&self) -> Self
+    {
+        Self(self.0.clone(), self.1.clone())
+    }
+}
+
+
+impl<'a, B, S, const DENSE_DEGREE: DegreeType> PartialEq for HybridVector<'a, B, S, DENSE_DEGREE>
+    where B: OrderedBasisWithDegree,
+          B::KeyType: Clone,
+          S: CoefficientRing
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 && self.1 == other.1
+    }
+}
+
+
+impl<'vec, 'a: 'vec, B, S, const DENSE_DEGREE: DegreeType> Vector<'vec> for HybridVector<'a, B, S, DENSE_DEGREE>
+    where B: 'static + OrderedBasisWithDegree,
+          B::KeyType: 'static + Clone,
+          S: 'static + CoefficientRing
+{
+    type BasisType = B;
+    type KeyType = B::KeyType;
+    type ScalarType = S;
+
+    fn new() -> Self {
+        Self(SimpleDenseVector::new(), OrderedSparseVector::new())
+    }
+
+    fn from_key(key: impl Into<Self::KeyType>) -> Self {
+        Self::from_key_scalar(key, S::one())
+    }
+
+    fn from_key_scalar(key: impl Into<Self::KeyType>, scalar: impl Into<Self::ScalarType>) -> Self {
+        let mut result = Self::new();
+        result.insert_single(&key.into(), scalar);
+        result
+    }
+
+    fn from_iterator(iterator: impl IntoIterator<Item=(Self::KeyType, Self::ScalarType)>) -> Self {
+        let (dense, sparse): (Vec<_>, Vec<_>) = iterator.into_iter()
+            .partition(|(k, _)| Self::is_dense_key(k));
+
+        Self(SimpleDenseVector::from_iterator(dense), OrderedSparseVector::from_iterator(sparse))
+    }
+
+    fn swap(&mut self, mut other: impl BorrowMut<Self>) {
+        let other = other.borrow_mut();
+        self.0.swap(&mut other.0);
+        self.1.swap(&mut other.1);
+    }
+
+    fn to_owned(&self) -> Self {
+        Self(Vector::to_owned(&self.0), self.1.clone())
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+        self.1.clear();
+    }
+
+    fn get(&self, key: impl Borrow<Self::KeyType>) -> Option<&Self::ScalarType> {
+        let k = key.borrow();
+        if Self::is_dense_key(k) {
+            self.0.get(k)
+        } else {
+            self.1.get(k)
+        }
+    }
+
+    fn get_mut(&mut self, key: impl Borrow<Self::KeyType>) -> Option<&mut Self::ScalarType> {
+        let k = key.borrow();
+        if Self::is_dense_key(k) {
+            self.0.get_mut(k)
+        } else {
+            self.1.get_mut(k)
+        }
+    }
+
+    fn insert_single(&mut self, key: &Self::KeyType, value: impl Into<Self::ScalarType>) {
+        if !Self::is_dense_key(key) {
+            self.1.insert_single(key, value);
+            return;
+        }
+
+        let index = B::key_to_index(key);
+        if index >= self.0.size() {
+            self.0.resize(B::vector_dimension_for_key(key));
+        }
+        self.0.as_mut_slice()[index] = value.into();
+    }
+
+    fn insert(&mut self, iterator: impl IntoIterator<Item=(Self::KeyType, Self::ScalarType)>) {
+        for (key, val) in iterator.into_iter() {
+            self.insert_single(&key, val);
+        }
+    }
+
+    fn erase(&mut self, key: impl Borrow<Self::KeyType>) {
+        let k = key.borrow();
+        if Self::is_dense_key(k) {
+            self.0.erase(k);
+        } else {
+            self.1.erase(k);
+        }
+    }
+
+    fn uminus_inplace(&mut self) -> &mut Self {
+        self.0.uminus_inplace();
+        self.1.uminus_inplace();
+        self
+    }
+
+    fn add_inplace(&mut self, other: impl Borrow<Self>) -> &mut Self {
+        let o = other.borrow();
+        self.0.add_inplace(&o.0);
+        self.1.add_inplace(&o.1);
+        self
+    }
+
+    fn sub_inplace(&mut self, other: impl Borrow<Self>) -> &mut Self {
+        let o = other.borrow();
+        self.0.sub_inplace(&o.0);
+        self.1.sub_inplace(&o.1);
+        self
+    }
+
+    fn scalar_lmultiply_inplace(&mut self, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        let s = scalar.into();
+        self.0.scalar_lmultiply_inplace(s.clone());
+        self.1.scalar_lmultiply_inplace(s);
+        self
+    }
+
+    fn scalar_rmultiply_inplace(&mut self, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        let s = scalar.into();
+        self.0.scalar_rmultiply_inplace(s.clone());
+        self.1.scalar_rmultiply_inplace(s);
+        self
+    }
+
+    fn add_scalar_rmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        let (o, s) = (other.borrow(), scalar.into());
+        self.0.add_scalar_rmultiply(&o.0, s.clone());
+        self.1.add_scalar_rmultiply(&o.1, s);
+        self
+    }
+
+    fn add_scalar_lmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        let (o, s) = (other.borrow(), scalar.into());
+        self.0.add_scalar_lmultiply(&o.0, s.clone());
+        self.1.add_scalar_lmultiply(&o.1, s);
+        self
+    }
+
+    fn scalar_rdivide_inplace_impl(&mut self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        self.0.scalar_rdivide_inplace_impl(rational);
+        self.1.scalar_rdivide_inplace_impl(rational);
+        self
+    }
+
+    fn add_scalar_rdivide_impl(&mut self, other: &Self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        self.0.add_scalar_rdivide_impl(&other.0, rational);
+        self.1.add_scalar_rdivide_impl(&other.1, rational);
+        self
+    }
+
+    fn sub_scalar_rdivide_impl(&mut self, other: &Self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        self.0.sub_scalar_rdivide_impl(&other.0, rational);
+        self.1.sub_scalar_rdivide_impl(&other.1, rational);
+        self
+    }
+
+    fn add_linear_combination(&mut self, terms: &[(&Self, Self::ScalarType)]) -> &mut Self {
+        let dense: Vec<_> = terms.iter().map(|(other, scalar)| (&other.0, scalar.clone())).collect();
+        self.0.add_linear_combination(&dense);
+        for (other, scalar) in terms {
+            self.1.add_scalar_rmultiply(&other.1, scalar.clone());
+        }
+        self
+    }
+}
+
+
+// The sparse part only holds keys above the dense degree, so its degree is zero only when it
+// has no non-zero coefficients.
+impl<'vec, 'a: 'vec, B, S, const DENSE_DEGREE: DegreeType> VectorWithDegree<'vec> for HybridVector<'a, B, S, DENSE_DEGREE>
+    where B: 'static + OrderedBasisWithDegree,
+          B::KeyType: 'static + Clone,
+          S: 'static + CoefficientRing
+{
+    fn degree(&self) -> DegreeType {
+        cmp::max(self.0.degree(), self.1.degree())
+    }
+}
+
+
+impl<'vec, 'a: 'vec, B, S, const DENSE_DEGREE: DegreeType> DegreeProjection<'vec> for HybridVector<'a, B, S, DENSE_DEGREE>
+    where B: 'static + OrderedBasisWithDegree,
+          B::KeyType: 'static + Clone,
+          S: 'static + CoefficientRing
+{
+    fn project(&self, degrees: impl RangeBounds<DegreeType>) -> Self {
+        let bounds = (degrees.start_bound().cloned(), degrees.end_bound().cloned());
+        HybridVector(self.0.project(bounds), self.1.project(bounds))
+    }
+
+    fn truncate(&mut self, degree: DegreeType) {
+        self.0.truncate(degree);
+        self.1.truncate(degree);
+    }
+}
+
+
+impl<'vec, 'a: 'vec, B, S, const DENSE_DEGREE: DegreeType> VectorApproxEq<'vec> for HybridVector<'a, B, S, DENSE_DEGREE>
+    where B: 'static + OrderedBasisWithDegree,
+          B::KeyType: 'static + Clone,
+          S: 'static + ApproxEq
+{
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        self.0.approx_eq(&other.0, tol) && self.1.approx_eq(&other.1, tol)
+    }
+}
+
+
+impl<'vec, 'a: 'vec, B, S, T, const DENSE_DEGREE: DegreeType> MapCoefficients<'vec, T> for HybridVector<'a, B, S, DENSE_DEGREE>
+    where B: 'static + OrderedBasisWithDegree,
+          B::KeyType: 'static + Clone,
+          S: 'static + CoefficientRing,
+          T: 'static + CoefficientRing
+{
+    type Output = HybridVector<'static, B, T, DENSE_DEGREE>;
+
+    fn map_coefficients(&self, mut func: impl FnMut(&S) -> T) -> Self::Output {
+        HybridVector(self.0.map_coefficients(&mut func), self.1.map_coefficients(func))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::free_tensor::{TensorBasis, TensorKey};
+
+    type TKey = TensorKey<2>;
+    type Vect<'a> = HybridVector<'a, TensorBasis<2>, f64, 1>;
+
+    #[test]
+    fn test_storage_split_by_degree() {
+        let vec = Vect::from_iterator(vec![
+            (TKey::new(), 1.0),
+            (TKey::from_letter(2), 2.0),
+            (TKey::from_letters(&[1, 2]), 3.0),
+            (TKey::from_letters(&[2, 2, 1]), 4.0),
+        ]);
+
+        assert_eq!(vec.dense_part().size(), Vect::dense_dimension());
+        assert_eq!(vec.sparse_part().len(), 2);
+        assert_eq!(vec.get(TKey::from_letter(2)), Some(&2.0));
+        assert_eq!(vec.get(TKey::from_letters(&[1, 2])), Some(&3.0));
+        assert_eq!(vec.degree(), 3);
+    }
+
+    #[test]
+    fn test_iteration_in_basis_order() {
+        let vec = Vect::from_iterator(vec![
+            (TKey::from_letters(&[2, 1]), 3.0),
+            (TKey::from_letter(1), 1.0),
+            (TKey::from_letters(&[1, 2]), 2.0),
+        ]);
+
+        let items: Vec<(TKey, f64)> = vec.iter().map(|(k, v)| (k, *v)).collect();
+        assert_eq!(items, vec![
+            (TKey::from_letter(1), 1.0),
+            (TKey::from_letters(&[1, 2]), 2.0),
+            (TKey::from_letters(&[2, 1]), 3.0),
+        ]);
+
+        assert_eq!(vec.iter_degree_range(..2).count(), 1);
+        assert_eq!(vec.iter_degree_range(2..=2).count(), 2);
+    }
+
+    #[test]
+    fn test_arithmetic_across_parts() {
+        let lhs = Vect::from_iterator(vec![(TKey::new(), 1.0), (TKey::from_letters(&[1, 1]), 2.0)]);
+        let rhs = Vect::from_iterator(vec![(TKey::from_letter(1), 1.0), (TKey::from_letters(&[1, 1]), 2.0)]);
+
+        let diff = lhs.sub(&rhs);
+        assert_eq!(diff.get(TKey::new()), Some(&1.0));
+        assert_eq!(diff.get(TKey::from_letter(1)), Some(&-1.0));
+        assert!(diff.sparse_part().is_empty());
+
+        let mut vec = lhs.scalar_lmultiply(2.0);
+        vec.insert_single(&TKey::from_letter(2), 5.0);
+        vec.erase(TKey::from_letters(&[1, 1]));
+        assert_eq!(vec, Vect::from_iterator(vec![(TKey::new(), 2.0), (TKey::from_letter(2), 5.0)]));
+    }
+
+    #[test]
+    fn test_project_and_truncate_across_split() {
+        let mut vec = Vect::from_iterator(vec![
+            (TKey::new(), 1.0),
+            (TKey::from_letter(2), 2.0),
+            (TKey::from_letters(&[1, 2]), 3.0),
+            (TKey::from_letters(&[2, 2, 1]), 4.0),
+        ]);
+
+        let projected = vec.project(1..=2);
+        assert_eq!(projected, Vect::from_iterator(vec![(TKey::from_letter(2), 2.0), (TKey::from_letters(&[1, 2]), 3.0)]));
+
+        vec.truncate(0);
+        assert_eq!(vec, Vect::from_key(TKey::new()));
+        assert_eq!(vec.degree(), 0);
+    }
+
+    #[test]
+    fn test_degree_skips_zero_set_through_get_mut() {
+        let mut vec = Vect::from_iterator(vec![(TKey::from_letter(1), 1.0), (TKey::from_letters(&[1, 2]), 3.0)]);
+        *vec.get_mut(TKey::from_letters(&[1, 2])).unwrap() = 0.0;
+
+        assert_eq!(vec.degree(), 1);
+        assert_eq!(vec, Vect::from_key(TKey::from_letter(1)));
+    }
+
+}
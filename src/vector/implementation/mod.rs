@@ -1,9 +1,9 @@
-pub use simple_dense_vector::SimpleDenseVector;
+pub use simple_dense_vector::{SimpleDenseVector, SimpleDenseIter, SimpleDenseIterMut, DegreeView, DenseVectorError};
 pub use simple_sparse_vector::SimpleSparseVector;
+pub use ordered_sparse_vector::{OrderedSparseVector, OrderedSparseIter};
+pub use hybrid_vector::HybridVector;
 
 mod simple_dense_vector;
 mod simple_sparse_vector;
-mod deref_impl;
-
-
-
+mod ordered_sparse_vector;
+mod hybrid_vector;
@@ -0,0 +1,596 @@
+use std::borrow::{Borrow, BorrowMut};
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+use std::iter::{IntoIterator, Map};
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+use std::slice::Iter as SliceIter;
+
+use crate::basis::{OrderedBasis, OrderedBasisWithDegree};
+use crate::coefficients::{ApproxEq, CoefficientField, CoefficientRing, ZeroPolicy};
+use crate::DegreeType;
+use crate::vector::{DegreeProjection, MapCoefficients, SparseVector, Vector, VectorApproxEq, VectorWithDegree};
+
+
+/// Iterator over the key-value pairs of an `OrderedSparseVector`.
+pub type OrderedSparseIter<'v, K, S> = Map<SliceIter<'v, (K, S)>, fn(&'v (K, S)) -> (&'v K, &'v S)>;
+
+
+/// Sparse vector storing the non-zero coefficients in a flat vector sorted by key.
+///
+/// Keys are ordered with `OrderedBasis::compare`, so iteration always visits the coefficients in
+/// basis order, unlike the hash map used by `SimpleSparseVector`. Lookups are binary searches,
+/// and arithmetic between two vectors merges their sorted storage in a single pass.
+///
+/// For bases with a degree, the basis order lists keys of lower degree first, so all the
+/// coefficients of a range of degrees are stored contiguously and can be visited without
+/// looking at the others.
+///
+/// As for `SimpleSparseVector`, arithmetic drops any coefficient that the `ZeroPolicy` of the
+/// vector considers to be zero.
+#[derive(Debug)]
+pub struct OrderedSparseVector<'a, B, S, K>(Vec<(K, S)>, ZeroPolicy<S>, PhantomData<&'a B>)
+    where B: OrderedBasis<KeyType=K>,
+          K: Clone,
+          S: CoefficientRing;
+
+
+impl<'a, B, S, K> OrderedSparseVector<'a, B, S, K>
+    where B: OrderedBasis<KeyType=K>,
+          K: Clone,
+          S: CoefficientRing
+{
+
+    pub fn with_zero_policy(mut self, policy: ZeroPolicy<S>) -> Self
+    {
+        self.set_zero_policy(policy);
+        self
+    }
+
+    /// Set the policy used to drop zero coefficients, and prune the vector under the new policy.
+    pub fn set_zero_policy(&mut self, policy: ZeroPolicy<S>)
+    {
+        self.1 = policy;
+        self.prune();
+    }
+
+    pub fn zero_policy(&self) -> &ZeroPolicy<S>
+    {
+        &self.1
+    }
+
+    /// Remove all coefficients that are zero under the zero policy of this vector.
+    pub fn prune(&mut self)
+    {
+        let policy = self.1;
+        self.0.retain(|(_, v)| !policy.is_zero(v));
+    }
+
+    /// Number of stored coefficients.
+    pub fn len(&self) -> usize
+    {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.0.is_empty()
+    }
+
+    /// Iterator over the stored key-value pairs in basis order.
+    pub fn iter(&self) -> OrderedSparseIter<'_, K, S>
+    {
+        self.0.iter().map(|(k, v)| (k, v))
+    }
+
+    /// Position of key in the storage, or the position at which it should be inserted.
+    fn search(&self, key: &K) -> Result<usize, usize>
+    {
+        self.0.binary_search_by(|(k, _)| B::compare(k, key))
+    }
+
+    /// Merge the sorted entries of other into self. Coefficients present in both are combined
+    /// with `combine` and coefficients only present in other are converted with `convert`.
+    fn merge(
+        &mut self,
+        other: &Self,
+        mut combine: impl FnMut(&mut S, &S),
+        mut convert: impl FnMut(&S) -> S
+    )
+    {
+        let policy = self.1;
+        let lhs = mem::take(&mut self.0);
+        let mut result = Vec::with_capacity(lhs.len() + other.0.len());
+
+        let mut lhs_iter = lhs.into_iter().peekable();
+        let mut rhs_iter = other.0.iter().peekable();
+
+        loop {
+            let order = match (lhs_iter.peek(), rhs_iter.peek()) {
+                (Some((lk, _)), Some((rk, _))) => B::compare(lk, rk),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break
+            };
+
+            match order {
+                Ordering::Less => result.push(lhs_iter.next().unwrap()),
+                Ordering::Greater => {
+                    let (k, v) = rhs_iter.next().unwrap();
+                    let val = convert(v);
+                    if !policy.is_zero(&val) {
+                        result.push((k.clone(), val));
+                    }
+                },
+                Ordering::Equal => {
+                    let (k, mut val) = lhs_iter.next().unwrap();
+                    combine(&mut val, &rhs_iter.next().unwrap().1);
+                    if !policy.is_zero(&val) {
+                        result.push((k, val));
+                    }
+                }
+            }
+        }
+
+        self.0 = result;
+    }
+}
+
+
+impl<'a, B, S, K> OrderedSparseVector<'a, B, S, K>
+    where B: OrderedBasisWithDegree<KeyType=K>,
+          K: Clone,
+          S: CoefficientRing
+{
+    /// Iterator over the key-value pairs whose keys have degree in `degrees`, in basis order.
+    pub fn iter_degree_range(&self, degrees: impl RangeBounds<DegreeType>) -> OrderedSparseIter<'_, K, S>
+    {
+        let begin = match degrees.start_bound() {
+            Bound::Included(d) => self.0.partition_point(|(k, _)| B::degree(k) < *d),
+            Bound::Excluded(d) => self.0.partition_point(|(k, _)| B::degree(k) <= *d),
+            Bound::Unbounded => 0
+        };
+        let end = match degrees.end_bound() {
+            Bound::Included(d) => self.0.partition_point(|(k, _)| B::degree(k) <= *d),
+            Bound::Excluded(d) => self.0.partition_point(|(k, _)| B::degree(k) < *d),
+            Bound::Unbounded => self.0.len()
+        };
+
+        self.0[begin..end.max(begin)].iter().map(|(k, v)| (k, v))
+    }
+
+    /// Iterator over the key-value pairs whose keys have degree `degree`, in basis order.
+    pub fn iter_degree(&self, degree: DegreeType) -> OrderedSparseIter<'_, K, S>
+    {
+        self.iter_degree_range(degree..=degree)
+    }
+}
+
+
+impl<'a, B, S, K> Clone for OrderedSparseVector<'a, B, S, K>
+    where B: OrderedBasis<KeyType=K>,
+          K: Clone,
+          S: CoefficientRing
+{
+    fn clone(&self) -> Self
+    {
+        Self(self.0.clone(), self.1, PhantomData)
+    }
+}
+
+
+// The storage is sorted, so equal vectors have equal storage once the coefficients that are
+// zero under the zero policy are skipped. These can be left in the storage by `get_mut`.
+impl<'a, B, S, K> PartialEq for OrderedSparseVector<'a, B, S, K>
+    where B: OrderedBasis<KeyType=K>,
+          K: Clone,
+          S: CoefficientRing
+{
+    fn eq(&self, other: &Self) -> bool {
+        let mut lhs = self.0.iter().filter(|(_, v)| !self.1.is_zero(v));
+        let mut rhs = other.0.iter().filter(|(_, v)| !other.1.is_zero(v));
+        loop {
+            match (lhs.next(), rhs.next()) {
+                (None, None) => return true,
+                (Some((lk, lv)), Some((rk, rv)))
+                    if B::compare(lk, rk) == Ordering::Equal && lv == rv => {},
+                _ => return false
+            }
+        }
+    }
+}
+
+
+impl<'vec, 'a: 'vec, B: 'vec, S: 'vec, K: 'vec> IntoIterator for &'vec OrderedSparseVector<'a, B, S, K>
+    where B: OrderedBasis<KeyType=K>,
+          K: 'a + Clone,
+          S: 'a + CoefficientRing
+{
+    type Item = (&'vec K, &'vec S);
+    type IntoIter = OrderedSparseIter<'vec, K, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+
+impl<'vec, 'a: 'vec, B, S, K> Vector<'vec> for OrderedSparseVector<'a, B, S, K>
+    where B: 'static + OrderedBasis<KeyType=K>,
+          K: 'static + Clone,
+          S: 'static + CoefficientRing
+{
+    type BasisType = B;
+    type KeyType = K;
+    type ScalarType = S;
+
+    fn new() -> Self {
+        Self(Vec::new(), ZeroPolicy::exact(), PhantomData)
+    }
+
+    fn from_key(key: impl Into<Self::KeyType>) -> Self {
+        Self::from_key_scalar(key, S::one())
+    }
+
+    fn from_key_scalar(key: impl Into<Self::KeyType>, scalar: impl Into<Self::ScalarType>) -> Self {
+        let mut result = Self::new();
+        result.insert_single(&key.into(), scalar);
+        result
+    }
+
+    // As for dense vectors, the values of repeated keys are added together.
+    fn from_iterator(iterator: impl IntoIterator<Item=(Self::KeyType, Self::ScalarType)>) -> Self {
+        let mut items: Vec<(K, S)> = iterator.into_iter().collect();
+        items.sort_by(|(k1, _), (k2, _)| B::compare(k1, k2));
+
+        let mut result: Vec<(K, S)> = Vec::with_capacity(items.len());
+        for (key, val) in items {
+            match result.last_mut() {
+                Some((k, v)) if B::compare(k, &key) == Ordering::Equal => { v.add_inplace(&val); },
+                _ => result.push((key, val))
+            }
+        }
+
+        let mut result = Self(result, ZeroPolicy::exact(), PhantomData);
+        result.prune();
+        result
+    }
+
+    fn swap(&mut self, mut other: impl BorrowMut<Self>) {
+        let other = other.borrow_mut();
+        mem::swap(&mut self.0, &mut other.0);
+        mem::swap(&mut self.1, &mut other.1);
+    }
+
+    fn to_owned(&self) -> Self {
+        self.clone()
+    }
+
+    fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    fn get(&self, key: impl Borrow<Self::KeyType>) -> Option<&Self::ScalarType> {
+        self.search(key.borrow()).ok().map(|i| &self.0[i].1)
+    }
+
+    // The coefficient may be set to zero through the reference, which leaves it in the storage
+    // until the next `prune`.
+    fn get_mut(&mut self, key: impl Borrow<Self::KeyType>) -> Option<&mut Self::ScalarType> {
+        match self.search(key.borrow()) {
+            Ok(i) => Some(&mut self.0[i].1),
+            Err(_) => None
+        }
+    }
+
+    fn insert_single(&mut self, key: &Self::KeyType, value: impl Into<Self::ScalarType>) {
+        let val = value.into();
+        let is_zero = self.1.is_zero(&val);
+
+        match self.search(key) {
+            Ok(i) if is_zero => { self.0.remove(i); },
+            Ok(i) => self.0[i].1 = val,
+            Err(_) if is_zero => {},
+            Err(i) => self.0.insert(i, (key.clone(), val))
+        }
+    }
+
+    fn insert(&mut self, iterator: impl IntoIterator<Item=(Self::KeyType, Self::ScalarType)>) {
+        for (key, val) in iterator.into_iter() {
+            self.insert_single(&key, val);
+        }
+    }
+
+    fn erase(&mut self, key: impl Borrow<Self::KeyType>) {
+        if let Ok(i) = self.search(key.borrow()) {
+            self.0.remove(i);
+        }
+    }
+
+    fn uminus_inplace(&mut self) -> &mut Self {
+        self.0.iter_mut().for_each(|(_, v)| { *v = S::uminus(v); });
+        self
+    }
+
+    fn add_inplace(&mut self, other: impl Borrow<Self>) -> &mut Self {
+        self.merge(other.borrow(), |l, r| { l.add_inplace(r); }, S::clone);
+        self
+    }
+
+    fn sub_inplace(&mut self, other: impl Borrow<Self>) -> &mut Self {
+        self.merge(other.borrow(), |l, r| { l.sub_inplace(r); }, S::uminus);
+        self
+    }
+
+    fn scalar_lmultiply_inplace(&mut self, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        let s = scalar.into();
+        if s == S::zero() {
+            self.0.clear();
+            return self;
+        }
+
+        self.0.iter_mut().for_each(|(_, v)| { v.lmul_inplace(&s); });
+        self.prune();
+        self
+    }
+
+    fn scalar_rmultiply_inplace(&mut self, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        let s = scalar.into();
+        if s == S::zero() {
+            self.0.clear();
+            return self;
+        }
+
+        self.0.iter_mut().for_each(|(_, v)| { v.mul_inplace(&s); });
+        self.prune();
+        self
+    }
+
+    fn add_scalar_rmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        let s = scalar.into();
+        self.merge(other.borrow(), |l, r| { l.add_inplace(&r.mul(&s)); }, |r| r.mul(&s));
+        self
+    }
+
+    fn add_scalar_lmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        let s = scalar.into();
+        self.merge(other.borrow(), |l, r| { l.add_inplace(&s.mul(r)); }, |r| s.mul(r));
+        self
+    }
+
+    fn scalar_rdivide_inplace_impl(&mut self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        self.0.iter_mut().for_each(|(_, v)| { v.div_inplace(rational); });
+        self.prune();
+        self
+    }
+
+    fn add_scalar_rdivide_impl(&mut self, other: &Self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        self.merge(other, |l, r| { l.add_inplace(&r.div(rational)); }, |r| r.div(rational));
+        self
+    }
+
+    fn sub_scalar_rdivide_impl(&mut self, other: &Self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        self.merge(other, |l, r| { l.sub_inplace(&r.div(rational)); }, |r| r.div(rational).uminus());
+        self
+    }
+}
+
+
+impl<'vec, 'a: 'vec, B, S, K> SparseVector<'vec> for OrderedSparseVector<'a, B, S, K>
+    where B: 'static + OrderedBasis<KeyType=K>,
+          K: 'static + Clone,
+          S: 'static + CoefficientRing
+{}
+
+
+// Keys are sorted by degree, so the last non-zero coefficient has the highest degree.
+impl<'vec, 'a: 'vec, B, S, K> VectorWithDegree<'vec> for OrderedSparseVector<'a, B, S, K>
+    where B: 'static + OrderedBasisWithDegree<KeyType=K>,
+          K: 'static + Clone,
+          S: 'static + CoefficientRing
+{
+    fn degree(&self) -> DegreeType {
+        self.0.iter().rev()
+            .find(|(_, v)| !self.1.is_zero(v))
+            .map_or(0, |(k, _)| B::degree(k))
+    }
+}
+
+
+impl<'vec, 'a: 'vec, B, S, K> DegreeProjection<'vec> for OrderedSparseVector<'a, B, S, K>
+    where B: 'static + OrderedBasisWithDegree<KeyType=K>,
+          K: 'static + Clone,
+          S: 'static + CoefficientRing
+{
+    fn project(&self, degrees: impl RangeBounds<DegreeType>) -> Self {
+        OrderedSparseVector(
+            self.iter_degree_range(degrees).map(|(k, v)| (k.clone(), v.clone())).collect(),
+            self.1,
+            PhantomData
+        )
+    }
+
+    fn truncate(&mut self, degree: DegreeType) {
+        let end = self.0.partition_point(|(k, _)| B::degree(k) <= degree);
+        self.0.truncate(end);
+    }
+}
+
+impl<'vec, 'a: 'vec, B, S, K> VectorApproxEq<'vec> for OrderedSparseVector<'a, B, S, K>
+    where B: 'static + OrderedBasis<KeyType=K>,
+          K: 'static + Clone,
+          S: 'static + ApproxEq
+{
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        let mut diff = self.clone().with_zero_policy(ZeroPolicy::exact());
+        diff.sub_inplace(other);
+        diff.0.iter().all(|(_, v)| v.is_approx_zero(tol))
+    }
+}
+
+
+impl<'vec, 'a: 'vec, B, S, T, K> MapCoefficients<'vec, T> for OrderedSparseVector<'a, B, S, K>
+    where B: 'static + OrderedBasis<KeyType=K>,
+          K: 'static + Clone,
+          S: 'static + CoefficientRing,
+          T: 'static + CoefficientRing
+{
+    type Output = OrderedSparseVector<'static, B, T, K>;
+
+    fn map_coefficients(&self, mut func: impl FnMut(&S) -> T) -> Self::Output {
+        let mut result = OrderedSparseVector(
+            self.0.iter().map(|(k, v)| (k.clone(), func(v))).collect(),
+            ZeroPolicy::exact(),
+            PhantomData
+        );
+        // Coefficients can become zero in the conversion, for instance when rounding.
+        result.prune();
+        result
+    }
+}
+
+
+impl<'a, B, S, K> Display for OrderedSparseVector<'a, B, S, K>
+    where B: OrderedBasis<KeyType=K>,
+          K: Clone + Display,
+          S: CoefficientRing + Display
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (k, v) in self.0.iter() {
+            write!(f, " {}{}", v, k)?;
+        }
+        write!(f, " }}")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::free_tensor::{TensorBasis, TensorKey};
+
+    type TKey = TensorKey<3>;
+    type TBasis = TensorBasis<3>;
+
+    type Vect<'a> = OrderedSparseVector<'a, TBasis, f64, TKey>;
+
+    #[test]
+    fn test_iteration_in_basis_order() {
+        let vec = Vect::from_iterator(vec![
+            (TKey::from_letters(&[2, 1]), 4.0),
+            (TKey::from_letter(3), 3.0),
+            (TKey::new(), 1.0),
+            (TKey::from_letter(1), 2.0),
+            (TKey::from_letters(&[1, 1]), 0.0),
+        ]);
+
+        let keys: Vec<TKey> = vec.iter().map(|(k, _)| k.clone()).collect();
+        assert_eq!(keys, vec![
+            TKey::new(),
+            TKey::from_letter(1),
+            TKey::from_letter(3),
+            TKey::from_letters(&[2, 1])
+        ]);
+
+        let values: Vec<f64> = (&vec).into_iter().map(|(_, v)| *v).collect();
+        assert_eq!(values, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_arithmetic_keeps_order() {
+        let lhs = Vect::from_iterator(vec![(TKey::new(), 1.0), (TKey::from_letters(&[1, 2]), 2.0)]);
+        let rhs = Vect::from_iterator(vec![(TKey::from_letter(2), 1.0), (TKey::from_letters(&[1, 2]), 2.0)]);
+
+        let diff = lhs.sub(&rhs);
+        let expected = Vect::from_iterator(vec![(TKey::new(), 1.0), (TKey::from_letter(2), -1.0)]);
+        assert_eq!(diff, expected);
+
+        let sum = lhs.add(&rhs);
+        let values: Vec<f64> = sum.iter().map(|(_, v)| *v).collect();
+        assert_eq!(values, vec![1.0, 1.0, 4.0]);
+        assert_eq!(sum.degree(), 2);
+    }
+
+    #[test]
+    fn test_insert_get_erase() {
+        let mut vec = Vect::new();
+
+        vec.insert(vec![(TKey::from_letter(2), 2.0), (TKey::new(), 1.0), (TKey::from_letter(1), 0.0)]);
+        assert_eq!(vec.len(), 2);
+        assert_eq!(vec.get(TKey::from_letter(2)), Some(&2.0));
+        assert_eq!(vec.get(TKey::from_letter(1)), None);
+
+        *vec.get_mut(TKey::new()).unwrap() = 5.0;
+        vec.erase(TKey::from_letter(2));
+        assert_eq!(vec, Vect::from_key_scalar(TKey::new(), 5.0));
+    }
+
+    #[test]
+    fn test_zero_set_through_get_mut() {
+        let mut vec = Vect::from_iterator(vec![(TKey::new(), 1.0), (TKey::from_letters(&[1, 2]), 2.0)]);
+        *vec.get_mut(TKey::from_letters(&[1, 2])).unwrap() = 0.0;
+
+        let expected = Vect::from_key_scalar(TKey::new(), 1.0);
+        assert_eq!(vec, expected);
+        assert_eq!(expected, vec);
+        assert_eq!(vec.degree(), 0);
+
+        vec.prune();
+        assert_eq!(vec.len(), 1);
+    }
+
+    #[test]
+    fn test_degree_range_queries() {
+        let vec = Vect::from_iterator(vec![
+            (TKey::new(), 1.0),
+            (TKey::from_letter(2), 2.0),
+            (TKey::from_letters(&[1, 3]), 3.0),
+            (TKey::from_letters(&[3, 1]), 4.0),
+            (TKey::from_letters(&[1, 1, 1]), 5.0),
+        ]);
+
+        let values = |itr: OrderedSparseIter<TKey, f64>| itr.map(|(_, v)| *v).collect::<Vec<_>>();
+
+        assert_eq!(values(vec.iter_degree(2)), vec![3.0, 4.0]);
+        assert_eq!(values(vec.iter_degree_range(1..3)), vec![2.0, 3.0, 4.0]);
+        assert_eq!(values(vec.iter_degree_range(2..)), vec![3.0, 4.0, 5.0]);
+        assert_eq!(values(vec.iter_degree_range(..=0)), vec![1.0]);
+        assert!(vec.iter_degree(4).next().is_none());
+    }
+
+    #[test]
+    fn test_project_and_truncate() {
+        let mut vec = Vect::from_iterator(vec![
+            (TKey::new(), 1.0),
+            (TKey::from_letter(2), 2.0),
+            (TKey::from_letters(&[1, 3]), 3.0),
+            (TKey::from_letters(&[1, 1, 1]), 5.0),
+        ]);
+
+        let projected = vec.project(1..=2);
+        assert_eq!(projected, Vect::from_iterator(vec![(TKey::from_letter(2), 2.0), (TKey::from_letters(&[1, 3]), 3.0)]));
+
+        vec.truncate(1);
+        assert_eq!(vec, Vect::from_iterator(vec![(TKey::new(), 1.0), (TKey::from_letter(2), 2.0)]));
+        assert_eq!(vec.degree(), 1);
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let lhs = Vect::from_iterator(vec![(TKey::new(), 1.0), (TKey::from_letter(1), 1e-14)]);
+        let rhs = Vect::from_iterator(vec![(TKey::new(), 1.0 + 1e-14)]);
+
+        assert_ne!(lhs, rhs);
+        assert!(lhs.approx_eq_default(&rhs));
+        assert!(!lhs.approx_eq(&rhs, 1e-16));
+    }
+}
@@ -1,25 +1,31 @@
 
 
+use std::error::Error;
 use std::fmt::{self, Display, Formatter};
 use std::cmp;
 use std::borrow::{Borrow, BorrowMut};
 use std::marker::PhantomData;
 
 use std::mem;
-use std::slice::{SliceIndex, Iter as SliceIter};
-use std::iter::{Zip, IntoIterator};
-use std::ops::Range;
+use std::slice::{SliceIndex, Iter as SliceIter, IterMut as SliceIterMut};
+use std::iter::{Enumerate, Zip, IntoIterator};
+use std::ops::RangeBounds;
 
-use crate::vector::{Vector, VectorIteratorItem};
-use crate::coefficients::{CoefficientField};
+use crate::vector::Vector;
+use crate::coefficients::{ApproxEq, CoefficientField, CoefficientRing, Magnitude};
 use crate::basis::{OrderedBasis, OrderedBasisWithDegree};
 use crate::{DimensionType, DegreeType};
-use crate::vector::{VectorWithDegree, DenseVector};
+use crate::vector::{VectorWithDegree, DegreeProjection, DenseVector, MapCoefficients, VectorApproxEq};
+use crate::vector::{GradedNormedVector, Norm, NormedVector};
 use crate::vector::traits::ResizeableDenseVector;
 
 
+/// Number of coefficients processed at a time when adding a linear combination of vectors.
+const LINEAR_COMBINATION_BLOCK: DimensionType = 1 << 10;
+
+
 #[derive(Debug, PartialEq)]
-enum SimpleDenseVectorData<'a, S: CoefficientField>
+enum SimpleDenseVectorData<'a, S: CoefficientRing>
 {
     Owned(Vec<S>),
     Borrowed(&'a [S]),
@@ -32,15 +38,31 @@ use SimpleDenseVectorData::*;
 
 
 
+/// Dense vector storing the coefficient of the key with index `i` at position `i`.
+///
+/// The coefficients are either owned by the vector or borrowed from a buffer supplied by the
+/// caller, with `from_slice` or `from_mut_slice`. Borrowing allows large buffers to be read,
+/// and used in arithmetic, without copying them. The storage follows these rules:
+///
+///  - A vector borrowing a shared buffer never writes to it. The first operation that mutates
+///    the vector, including `as_mut_slice`, copies the coefficients into owned storage.
+///  - A vector borrowing a mutable buffer writes to it in place. Operations that need to
+///    increase the dimension, such as `resize`, adding a longer vector, or inserting a key
+///    beyond the end, copy the coefficients into owned storage, after which the buffer is no
+///    longer updated.
+///  - Reducing the dimension of a borrowing vector, with `resize` or `truncate`, shortens the
+///    borrow without copying.
+///
+/// Use `is_owned` to check whether a vector still refers to its buffer.
 #[derive(Debug)]
-pub struct SimpleDenseVector<'a, B: OrderedBasis, S: CoefficientField>(
+pub struct SimpleDenseVector<'a, B: OrderedBasis, S: CoefficientRing>(
     SimpleDenseVectorData<'a, S>, PhantomData<B>
 );
 
 
 
 impl<'a, B, S> Clone for SimpleDenseVector<'a, B, S>
-    where B: OrderedBasis, S: CoefficientField
+    where B: OrderedBasis, S: CoefficientRing
 {
     fn clone(&self) -> Self
     {
@@ -52,7 +74,7 @@ impl<'a, B, S> Clone for SimpleDenseVector<'a, B, S>
     }
 }
 
-impl<'a, B: OrderedBasis, S: CoefficientField> SimpleDenseVector<'a, B, S> {
+impl<'a, B: OrderedBasis, S: CoefficientRing> SimpleDenseVector<'a, B, S> {
 
     pub fn new() -> SimpleDenseVector<'a, B, S>
     {
@@ -66,7 +88,7 @@ impl<'a, B: OrderedBasis, S: CoefficientField> SimpleDenseVector<'a, B, S> {
 
     pub fn from_dimension(size: DimensionType) -> SimpleDenseVector<'a, B, S>
     {
-        SimpleDenseVector(Owned(vec![S::ZERO; size]), PhantomData)
+        SimpleDenseVector(Owned(vec![S::zero(); size]), PhantomData)
     }
 
     fn to_owned_with_size(&mut self, resize: Option<DimensionType>)
@@ -81,7 +103,7 @@ impl<'a, B: OrderedBasis, S: CoefficientField> SimpleDenseVector<'a, B, S> {
         }
 
         if let Some(dim) = resize {
-            new_vec.resize(dim, S::ZERO);
+            new_vec.resize(dim, S::zero());
         }
 
 
@@ -97,10 +119,47 @@ impl<'a, B: OrderedBasis, S: CoefficientField> SimpleDenseVector<'a, B, S> {
             Borrowed(v) => v.len()
         }
     }
+
+    /// Whether the coefficients are owned by the vector rather than borrowed from a buffer.
+    pub fn is_owned(&self) -> bool
+    {
+        matches!(self.0, Owned(_))
+    }
+
+    /// Convert into a vector that owns its coefficients, copying them if they are borrowed.
+    pub fn into_owned(self) -> SimpleDenseVector<'static, B, S>
+    {
+        SimpleDenseVector(match self.0 {
+            Owned(v) => Owned(v),
+            Borrowed(v) => Owned(v.to_vec()),
+            BorrowedMut(v) => Owned(v.to_vec())
+        }, PhantomData)
+    }
+}
+
+
+/// Error returned when a buffer cannot be used as the coefficients of a dense vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DenseVectorError {
+    /// The length of the buffer is not the dimension of the keys of degree less than some
+    /// degree.
+    NotDegreeBoundary(DimensionType)
+}
+
+impl Display for DenseVectorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DenseVectorError::NotDegreeBoundary(len) =>
+                write!(f, "buffer of length {} does not end at the start of a degree", len)
+        }
+    }
 }
 
+impl Error for DenseVectorError {}
+
 
-impl<'a, B: OrderedBasisWithDegree, S: CoefficientField> SimpleDenseVector<'a, B, S> {
+impl<'a, B: OrderedBasisWithDegree, S: CoefficientRing> SimpleDenseVector<'a, B, S> {
 
     fn from_degree(deg: DegreeType) -> Self
     {
@@ -108,9 +167,43 @@ impl<'a, B: OrderedBasisWithDegree, S: CoefficientField> SimpleDenseVector<'a, B
         Self::from_dimension(dim)
     }
 
+    fn check_dimension(dim: DimensionType) -> Result<(), DenseVectorError>
+    {
+        let mut deg = 0;
+        while B::start_of_degree(deg) < dim {
+            deg += 1;
+        }
+
+        if B::start_of_degree(deg) == dim {
+            Ok(())
+        } else {
+            Err(DenseVectorError::NotDegreeBoundary(dim))
+        }
+    }
+
+    /// Wrap a buffer holding all the coefficients of the keys of degree below some degree.
+    ///
+    /// The buffer is borrowed, not copied, and the vector never writes to it.
+    pub fn from_slice(data: &'a [S]) -> Result<Self, DenseVectorError>
+    {
+        Self::check_dimension(data.len())?;
+        Ok(SimpleDenseVector(Borrowed(data), PhantomData))
+    }
+
+    /// Wrap a mutable buffer holding all the coefficients of the keys of degree below some
+    /// degree.
+    ///
+    /// The buffer is borrowed, not copied, and operations on the vector update it in place as
+    /// long as they do not increase the dimension.
+    pub fn from_mut_slice(data: &'a mut [S]) -> Result<Self, DenseVectorError>
+    {
+        Self::check_dimension(data.len())?;
+        Ok(SimpleDenseVector(BorrowedMut(data), PhantomData))
+    }
+
 }
 
-impl<'a, B: OrderedBasis, S: CoefficientField> PartialEq for SimpleDenseVector<'a, B, S> {
+impl<'a, B: OrderedBasis, S: CoefficientRing> PartialEq for SimpleDenseVector<'a, B, S> {
     fn eq(&self, other: &Self) -> bool {
         let a = match &self.0 {
             Owned(v) => v.as_slice(),
@@ -124,12 +217,17 @@ impl<'a, B: OrderedBasis, S: CoefficientField> PartialEq for SimpleDenseVector<'
             Borrowed(v ) => v
         };
 
-        a == b
+        // Vectors of different sizes are equal if the additional entries in the larger vector
+        // are all zero.
+        let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+        let (common, tail) = longer.split_at(shorter.len());
+
+        shorter == common && tail.iter().all(|v| *v == S::zero())
     }
 }
 
 
-impl<'a, B: OrderedBasis, S: CoefficientField> SimpleDenseVector<'a, B, S> {
+impl<'a, B: OrderedBasis, S: CoefficientRing> SimpleDenseVector<'a, B, S> {
 
     pub(crate) fn as_slice(&self) -> &[S]
     {
@@ -153,26 +251,283 @@ impl<'a, B: OrderedBasis, S: CoefficientField> SimpleDenseVector<'a, B, S> {
         }
     }
 
+    /// Iterate over the keys of the basis together with the corresponding coefficients, in
+    /// basis order. Keys are computed from the index of each coefficient.
+    pub fn iter(&self) -> SimpleDenseIter<'_, B, S>
+    {
+        SimpleDenseIter::new(self.as_slice(), false)
+    }
+
+    /// Iterate over the keys and coefficients of the non-zero coefficients, in basis order.
+    pub fn iter_nonzero(&self) -> SimpleDenseIter<'_, B, S>
+    {
+        SimpleDenseIter::new(self.as_slice(), true)
+    }
+
+    /// Iterate over the keys of the basis together with mutable references to the
+    /// corresponding coefficients, in basis order.
+    pub fn iter_mut(&mut self) -> SimpleDenseIterMut<'_, B, S>
+    {
+        SimpleDenseIterMut::new(self.as_mut_slice(), false)
+    }
+
+    /// Iterate over the keys and mutable coefficients of the non-zero coefficients, in basis
+    /// order. Coefficients that are zero when reached are skipped.
+    pub fn iter_nonzero_mut(&mut self) -> SimpleDenseIterMut<'_, B, S>
+    {
+        SimpleDenseIterMut::new(self.as_mut_slice(), true)
+    }
+
+}
+
+
+/// Iterator over the key-coefficient pairs of a `SimpleDenseVector`.
+pub struct SimpleDenseIter<'v, B: OrderedBasis, S: CoefficientRing>
+{
+    inner: Enumerate<SliceIter<'v, S>>,
+    zero: Option<S>,
+    basis: PhantomData<B>
 }
 
+impl<'v, B: OrderedBasis, S: CoefficientRing> SimpleDenseIter<'v, B, S> {
+    fn new(data: &'v [S], skip_zeros: bool) -> Self
+    {
+        Self {
+            inner: data.iter().enumerate(),
+            zero: if skip_zeros { Some(S::zero()) } else { None },
+            basis: PhantomData
+        }
+    }
+}
+
+impl<'v, B: OrderedBasis, S: CoefficientRing> Iterator for SimpleDenseIter<'v, B, S> {
+    type Item = (B::KeyType, &'v S);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let zero = &self.zero;
+        self.inner
+            .find(|(_, v)| zero.as_ref().is_none_or(|z| *v != z))
+            .map(|(i, v)| (B::index_to_key(i), v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.inner.size_hint();
+        if self.zero.is_some() { (0, upper) } else { (lower, upper) }
+    }
+}
+
+
+/// Iterator over the keys and mutable coefficients of a `SimpleDenseVector`.
+pub struct SimpleDenseIterMut<'v, B: OrderedBasis, S: CoefficientRing>
+{
+    inner: Enumerate<SliceIterMut<'v, S>>,
+    zero: Option<S>,
+    basis: PhantomData<B>
+}
+
+impl<'v, B: OrderedBasis, S: CoefficientRing> SimpleDenseIterMut<'v, B, S> {
+    fn new(data: &'v mut [S], skip_zeros: bool) -> Self
+    {
+        Self {
+            inner: data.iter_mut().enumerate(),
+            zero: if skip_zeros { Some(S::zero()) } else { None },
+            basis: PhantomData
+        }
+    }
+}
+
+impl<'v, B: OrderedBasis, S: CoefficientRing> Iterator for SimpleDenseIterMut<'v, B, S> {
+    type Item = (B::KeyType, &'v mut S);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let zero = &self.zero;
+        self.inner
+            .find(|(_, v)| zero.as_ref().is_none_or(|z| &**v != z))
+            .map(|(i, v)| (B::index_to_key(i), v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.inner.size_hint();
+        if self.zero.is_some() { (0, upper) } else { (lower, upper) }
+    }
+}
+
+impl<'v, 'a, B: OrderedBasis, S: CoefficientRing> IntoIterator for &'v SimpleDenseVector<'a, B, S> {
+    type Item = (B::KeyType, &'v S);
+    type IntoIter = SimpleDenseIter<'v, B, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'v, 'a, B: OrderedBasis, S: CoefficientRing> IntoIterator for &'v mut SimpleDenseVector<'a, B, S> {
+    type Item = (B::KeyType, &'v mut S);
+    type IntoIter = SimpleDenseIterMut<'v, B, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+
+
+
+
+impl<'a, B: OrderedBasisWithDegree, S: CoefficientRing> SimpleDenseVector<'a, B, S> {
+
+    /// View of the component of degree `degree`.
+    ///
+    /// The view holds the coefficients of the keys of this degree that are stored in the
+    /// vector, so it is shorter than the full degree if the vector is.
+    pub fn degree_view(&self, degree: DegreeType) -> DegreeView<'_, B, S>
+    {
+        let range = B::degree_index_range(degree..=degree, self.size());
+        DegreeView {
+            degree,
+            data: Borrowed(&self.as_slice()[range]),
+            basis: PhantomData
+        }
+    }
+
+    /// Mutable view of the component of degree `degree`, which writes through to the vector.
+    ///
+    /// The vector is first resized to hold all of the keys of this degree, if it does not
+    /// already do so. As with `resize`, this copies borrowed coefficients.
+    pub fn degree_view_mut(&mut self, degree: DegreeType) -> DegreeView<'_, B, S>
+    {
+        let end = B::start_of_degree(degree + 1);
+        if self.size() < end {
+            match &mut self.0 {
+                Owned(v) => v.resize(end, S::zero()),
+                BorrowedMut(_) | Borrowed(_) => self.to_owned_with_size(Some(end))
+            }
+        }
+
+        let range = B::degree_index_range(degree..=degree, end);
+        DegreeView {
+            degree,
+            data: BorrowedMut(&mut self.as_mut_slice()[range]),
+            basis: PhantomData
+        }
+    }
+}
+
+
+/// Homogeneous component of a `SimpleDenseVector`.
+///
+/// Views are created by `SimpleDenseVector::degree_view` and `SimpleDenseVector::degree_view_mut`,
+/// and store their coefficients in the same way as the vector. A view from `degree_view_mut`
+/// writes through to the vector. A view from `degree_view` is read-only, and as with a borrowed
+/// `SimpleDenseVector`, mutating it first copies the coefficients.
+#[derive(Debug)]
+pub struct DegreeView<'a, B: OrderedBasisWithDegree, S: CoefficientRing>
+{
+    degree: DegreeType,
+    data: SimpleDenseVectorData<'a, S>,
+    basis: PhantomData<B>
+}
+
+impl<'a, B: OrderedBasisWithDegree, S: CoefficientRing> DegreeView<'a, B, S> {
+
+    pub fn degree(&self) -> DegreeType
+    {
+        self.degree
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.as_slice().is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[S]
+    {
+        match &self.data {
+            Owned(v) => v.as_slice(),
+            Borrowed(v) => v,
+            BorrowedMut(v) => v
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [S]
+    {
+        if let Borrowed(v) = self.data {
+            self.data = Owned(v.to_vec());
+        }
+
+        match &mut self.data {
+            Owned(v) => v.as_mut_slice(),
+            BorrowedMut(v) => v,
+            Borrowed(_) => unreachable!()
+        }
+    }
+
+    fn position(&self, key: &B::KeyType) -> Option<usize>
+    {
+        if B::degree(key) == self.degree {
+            Some(B::key_to_index(key) - B::start_of_degree(self.degree))
+        } else {
+            None
+        }
+    }
+
+    /// Coefficient of `key`, or `None` if the key does not belong to this component.
+    pub fn get(&self, key: impl Borrow<B::KeyType>) -> Option<&S>
+    {
+        self.position(key.borrow()).and_then(|i| self.as_slice().get(i))
+    }
 
+    pub fn get_mut(&mut self, key: impl Borrow<B::KeyType>) -> Option<&mut S>
+    {
+        match self.position(key.borrow()) {
+            Some(i) => self.as_mut_slice().get_mut(i),
+            None => None
+        }
+    }
+
+    /// Iterate over the keys of this degree together with their coefficients.
+    pub fn iter(&self) -> impl Iterator<Item=(B::KeyType, &S)> + '_
+    {
+        let offset = B::start_of_degree(self.degree);
+        self.as_slice().iter().enumerate().map(move |(i, v)| (B::index_to_key(offset + i), v))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item=(B::KeyType, &mut S)> + '_
+    {
+        let offset = B::start_of_degree(self.degree);
+        self.as_mut_slice().iter_mut().enumerate().map(move |(i, v)| (B::index_to_key(offset + i), v))
+    }
+
+    /// Copy of this component as a vector.
+    pub fn to_vector(&self) -> SimpleDenseVector<'static, B, S>
+    {
+        let offset = B::start_of_degree(self.degree);
+        let mut data = vec![S::zero(); offset];
+        data.extend_from_slice(self.as_slice());
+        SimpleDenseVector(Owned(data), PhantomData)
+    }
+}
 
 
 impl<'vec, 'a: 'vec, B, S> Vector<'vec> for SimpleDenseVector<'a, B, S>
     where B: 'static + OrderedBasis,
-          S: 'static + CoefficientField
+          S: 'static + CoefficientRing
 {
     type BasisType = B;
     type KeyType = B::KeyType;
     type ScalarType = S;
-    type RationalType = S::RationalType;
 
     fn new() -> Self {
         Self::new()
     }
 
     fn from_key(key: impl Into<Self::KeyType>) -> Self {
-        Self::from_key_scalar(key, Self::ScalarType::ONE)
+        Self::from_key_scalar(key, Self::ScalarType::one())
     }
 
     fn from_key_scalar(key: impl Into<Self::KeyType>, scalar: impl Into<Self::ScalarType>) -> Self {
@@ -224,7 +579,7 @@ impl<'vec, 'a: 'vec, B, S> Vector<'vec> for SimpleDenseVector<'a, B, S>
     fn clear(&mut self) {
         match &mut self.0 {
             Owned(v) => v.clear(),
-            BorrowedMut(v) => {v.fill(S::ZERO)},
+            BorrowedMut(v) => {v.fill(S::zero())},
             Borrowed(_) => self.0 = Owned(Vec::new())
         };
     }
@@ -241,17 +596,22 @@ impl<'vec, 'a: 'vec, B, S> Vector<'vec> for SimpleDenseVector<'a, B, S>
     {
         let idx = B::key_to_index(key);
 
-
+        if idx >= self.size() {
+            self.resize(B::vector_dimension_for_key(key));
+        }
+        self.as_mut_slice()[idx] = value.into();
     }
 
     fn insert(&mut self, iterator: impl IntoIterator<Item=(Self::KeyType, Self::ScalarType)>) {
-        todo!()
+        for (key, val) in iterator.into_iter() {
+            self.insert_single(&key, val);
+        }
     }
 
     fn erase(&mut self, key: impl Borrow<Self::KeyType>)
     {
         if let Some(v) = self.get_mut(key) {
-            *v = S::ZERO;
+            *v = S::zero();
         }
     }
 
@@ -273,9 +633,7 @@ impl<'vec, 'a: 'vec, B, S> Vector<'vec> for SimpleDenseVector<'a, B, S>
             self.resize(lhs_vec.size());
         }
 
-        for (lhs, rhs) in self.as_mut_slice().iter_mut().zip(lhs_vec.as_slice()) {
-            Self::ScalarType::add_inplace(lhs, rhs);
-        }
+        S::add_slice_inplace(self.as_mut_slice(), lhs_vec.as_slice());
 
         self
     }
@@ -287,9 +645,7 @@ impl<'vec, 'a: 'vec, B, S> Vector<'vec> for SimpleDenseVector<'a, B, S>
             self.resize(lhs_vec.size());
         }
 
-        for (lhs, rhs) in self.as_mut_slice().iter_mut().zip(lhs_vec.as_slice()) {
-            Self::ScalarType::sub_inplace(lhs, rhs);
-        }
+        S::sub_slice_inplace(self.as_mut_slice(), lhs_vec.as_slice());
 
         self
     }
@@ -301,18 +657,103 @@ impl<'vec, 'a: 'vec, B, S> Vector<'vec> for SimpleDenseVector<'a, B, S>
             self.0 = Owned(v.to_vec());
         }
 
+        S::lmul_slice_inplace(self.as_mut_slice(), &val);
+
+        self
+    }
+
+    fn scalar_rmultiply_inplace(&mut self, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        let val = scalar.into();
+
+        if let Borrowed(v) = self.0 {
+            self.0 = Owned(v.to_vec());
+        }
+
+        S::mul_slice_inplace(self.as_mut_slice(), &val);
+
+        self
+    }
+
+    fn scalar_rdivide_inplace_impl(&mut self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
         for lhs in self.as_mut_slice() {
-            Self::ScalarType::mul_inplace(lhs, &val);
+            lhs.div_inplace(rational);
         }
 
         self
     }
 
-    fn scalar_rdivide_inplace(&mut self, rational: impl Into<Self::RationalType>) -> &mut Self {
-        let val = rational.into();
+    fn add_scalar_rmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        let other = other.borrow();
 
-        for lhs in self.as_mut_slice() {
-            Self::ScalarType::div_inplace(lhs, &val);
+        if other.size() > self.size() {
+            self.resize(other.size());
+        }
+
+        S::add_mul_slice_inplace(self.as_mut_slice(), other.as_slice(), &scalar.into());
+
+        self
+    }
+
+    fn add_scalar_lmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        let other = other.borrow();
+
+        if other.size() > self.size() {
+            self.resize(other.size());
+        }
+
+        S::add_lmul_slice_inplace(self.as_mut_slice(), other.as_slice(), &scalar.into());
+
+        self
+    }
+
+    fn add_scalar_rdivide_impl(&mut self, other: &Self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        if other.size() > self.size() {
+            self.resize(other.size());
+        }
+
+        for (lhs, rhs) in self.as_mut_slice().iter_mut().zip(other.as_slice()) {
+            lhs.add_inplace(&rhs.div(rational));
+        }
+
+        self
+    }
+
+    fn sub_scalar_rdivide_impl(&mut self, other: &Self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        if other.size() > self.size() {
+            self.resize(other.size());
+        }
+
+        for (lhs, rhs) in self.as_mut_slice().iter_mut().zip(other.as_slice()) {
+            lhs.sub_inplace(&rhs.div(rational));
+        }
+
+        self
+    }
+
+    // The terms are added a block of coefficients at a time, so that each block of self stays
+    // in cache while every term is added to it.
+    fn add_linear_combination(&mut self, terms: &[(&Self, Self::ScalarType)]) -> &mut Self {
+        let size = terms.iter().map(|(other, _)| other.size()).max().unwrap_or(0);
+
+        if size > self.size() {
+            self.resize(size);
+        }
+
+        let out = self.as_mut_slice();
+        for start in (0..size).step_by(LINEAR_COMBINATION_BLOCK) {
+            let block = &mut out[start..cmp::min(start + LINEAR_COMBINATION_BLOCK, size)];
+
+            for (other, scalar) in terms {
+                if let Some(rhs) = other.as_slice().get(start..) {
+                    S::add_mul_slice_inplace(block, rhs, scalar);
+                }
+            }
         }
 
         self
@@ -323,7 +764,7 @@ impl<'vec, 'a: 'vec, B, S> Vector<'vec> for SimpleDenseVector<'a, B, S>
 
 impl<'vec, 'a: 'vec, B, S> DenseVector<'vec> for SimpleDenseVector<'a, B, S>
     where B: 'static + OrderedBasis,
-          S: 'static + CoefficientField
+          S: 'static + CoefficientRing
 {
     fn as_slice(&self) -> &[Self::ScalarType] {
         match &self.0 {
@@ -348,12 +789,18 @@ impl<'vec, 'a: 'vec, B, S> DenseVector<'vec> for SimpleDenseVector<'a, B, S>
 
 impl<'vec, 'a: 'vec, B, S> ResizeableDenseVector<'vec> for SimpleDenseVector<'a, B, S>
     where B: 'static + OrderedBasis,
-          S: 'static + CoefficientField
+          S: 'static + CoefficientRing
 {
     fn resize(&mut self, new_dim: DimensionType)
     {
+        // Borrowed data only has to be copied if it must grow.
         match &mut self.0 {
-            Owned(v) => v.resize(new_dim, S::ZERO),
+            Owned(v) => v.resize(new_dim, S::zero()),
+            Borrowed(v) if new_dim <= v.len() => *v = &v[..new_dim],
+            BorrowedMut(v) if new_dim <= v.len() => {
+                let data = mem::take(v);
+                *v = &mut data[..new_dim];
+            }
             BorrowedMut(_) | Borrowed(_) => Self::to_owned_with_size(self, Some(new_dim))
         }
     }
@@ -362,7 +809,7 @@ impl<'vec, 'a: 'vec, B, S> ResizeableDenseVector<'vec> for SimpleDenseVector<'a,
 
 impl<'vec, 'a: 'vec, B, S> VectorWithDegree<'vec> for SimpleDenseVector<'a, B, S>
     where B: 'static + OrderedBasisWithDegree,
-          S: 'static + CoefficientField
+          S: 'static + CoefficientRing
 {
     fn degree(&self) -> DegreeType {
         let size = self.size();
@@ -375,9 +822,84 @@ impl<'vec, 'a: 'vec, B, S> VectorWithDegree<'vec> for SimpleDenseVector<'a, B, S
 
 }
 
+impl<'vec, 'a: 'vec, B, S> DegreeProjection<'vec> for SimpleDenseVector<'a, B, S>
+    where B: 'static + OrderedBasisWithDegree,
+          S: 'static + CoefficientRing
+{
+    fn project(&self, degrees: impl RangeBounds<DegreeType>) -> Self {
+        let range = B::degree_index_range(degrees, self.size());
+        let mut result = Self::from_dimension(range.end);
+        result.as_mut_slice()[range.clone()].clone_from_slice(&self.as_slice()[range]);
+        result
+    }
+
+    fn truncate(&mut self, degree: DegreeType) {
+        if degree >= self.degree() {
+            return;
+        }
+        self.resize(B::start_of_degree(degree + 1));
+    }
+}
+
+impl<'vec, 'a: 'vec, B, S> VectorApproxEq<'vec> for SimpleDenseVector<'a, B, S>
+    where B: 'static + OrderedBasis,
+          S: 'static + ApproxEq
+{
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        let (a, b) = (self.as_slice(), other.as_slice());
+
+        // As with exact equality, the additional entries of the longer vector must be zero.
+        let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+        let (common, tail) = longer.split_at(shorter.len());
+
+        shorter.iter().zip(common).all(|(l, r)| l.approx_eq(r, tol))
+            && tail.iter().all(|v| v.is_approx_zero(tol))
+    }
+}
+
+impl<'vec, 'a: 'vec, B, S> NormedVector<'vec> for SimpleDenseVector<'a, B, S>
+    where B: 'static + OrderedBasis,
+          S: 'static + Magnitude
+{
+    fn norm(&self, norm: Norm) -> f64 {
+        norm.of_coefficients(self.as_slice())
+    }
+
+    fn inner_product(&self, other: &Self) -> S {
+        // Coefficients beyond the end of the shorter vector are zero.
+        let mut result = S::zero();
+        for (lhs, rhs) in self.as_slice().iter().zip(other.as_slice()) {
+            result.add_inplace(&lhs.mul(rhs));
+        }
+        result
+    }
+}
+
+impl<'vec, 'a: 'vec, B, S> GradedNormedVector<'vec> for SimpleDenseVector<'a, B, S>
+    where B: 'static + OrderedBasisWithDegree,
+          S: 'static + Magnitude
+{
+    fn degree_norm(&self, degree: DegreeType, norm: Norm) -> f64 {
+        let data = self.as_slice();
+        norm.of_coefficients(&data[B::degree_index_range(degree..=degree, data.len())])
+    }
+}
+
+impl<'vec, 'a: 'vec, B, S, T> MapCoefficients<'vec, T> for SimpleDenseVector<'a, B, S>
+    where B: 'static + OrderedBasis,
+          S: 'static + CoefficientRing,
+          T: 'static + CoefficientRing
+{
+    type Output = SimpleDenseVector<'static, B, T>;
+
+    fn map_coefficients(&self, func: impl FnMut(&S) -> T) -> Self::Output {
+        SimpleDenseVector(Owned(self.as_slice().iter().map(func).collect()), PhantomData)
+    }
+}
+
 impl<'a, B, S, K> Display for SimpleDenseVector<'a, B, S>
     where B: 'a + OrderedBasis<KeyType = K>,
-          S: 'a + CoefficientField + Display,
+          S: 'a + CoefficientRing + Display,
           K: Display
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -396,6 +918,7 @@ mod tests {
     use super::*;
     use crate::basis::Basis;
     use std::cmp::Ordering;
+    use std::ops::Range;
 
     struct IntegerBasis;
 
@@ -543,6 +1066,67 @@ mod tests {
         assert_eq!(result.as_slice(), expected.as_slice());
     }
 
+    #[test]
+    fn test_scalar_divide_divides_each_coefficient() {
+        // Multiplying by the rounded inverse of 3 gives different results for some of these.
+        let values: Vec<f32> = (1..=20).map(|i| i as f32).collect();
+        let quotients: Vec<f32> = values.iter().map(|v| v / 3.0).collect();
+        let v1 = DenseVec::from(values);
+
+        assert_eq!(v1.scalar_rdivide(3.0f32).as_slice(), quotients.as_slice());
+        assert_eq!(v1.scalar_ldivide(3.0f32).as_slice(), quotients.as_slice());
+
+        let mut result = DenseVec::new();
+        result.add_scalar_rdivide(&v1, 3.0f32);
+        assert_eq!(result.as_slice(), quotients.as_slice());
+
+        result.sub_scalar_ldivide(&v1, 3.0f32);
+        assert!(result.as_slice().iter().all(|v| *v == 0.0));
+    }
+
+    #[test]
+    fn test_convert_coefficients() {
+        let v1 = DenseVec::from(vec![1.0f32, 0.5, -2.25]);
 
+        let result: SimpleDenseVector<IntegerBasis, f64> = v1.convert_coefficients();
 
-}
\ No newline at end of file
+        assert_eq!(result.as_slice(), &[1.0f64, 0.5, -2.25]);
+        assert_eq!(v1.map_coefficients(|v| *v as i64 * 2).as_slice(), &[2i64, 0, -4]);
+    }
+
+    #[test]
+    fn test_iterators() {
+        let mut v1 = DenseVec::from(vec![1.0f32, 0.0, -2.0, 0.0]);
+
+        let all: Vec<(u8, f32)> = v1.iter().map(|(k, v)| (k, *v)).collect();
+        assert_eq!(all, vec![(0, 1.0), (1, 0.0), (2, -2.0), (3, 0.0)]);
+
+        let nonzero: Vec<(u8, f32)> = v1.iter_nonzero().map(|(k, v)| (k, *v)).collect();
+        assert_eq!(nonzero, vec![(0, 1.0), (2, -2.0)]);
+
+        for (k, v) in v1.iter_nonzero_mut() {
+            *v += k as f32;
+        }
+        for (k, v) in &mut v1 {
+            if k == 3 {
+                *v = 5.0;
+            }
+        }
+        assert_eq!(v1.as_slice(), &[1.0f32, 0.0, 0.0, 5.0]);
+    }
+
+    #[test]
+    fn test_norms_and_inner_product() {
+        let v1 = DenseVec::from(vec![3.0f32, -4.0, 0.0]);
+        let v2 = DenseVec::from(vec![1.0f32, 2.0]);
+
+        assert_eq!(v1.l1_norm(), 7.0);
+        assert_eq!(v1.l2_norm(), 5.0);
+        assert_eq!(v1.linf_norm(), 4.0);
+        assert_eq!(DenseVec::new().linf_norm(), 0.0);
+
+        assert_eq!(v1.inner_product(&v2), -5.0);
+        assert_eq!(v2.inner_product(&v1), -5.0);
+    }
+
+}
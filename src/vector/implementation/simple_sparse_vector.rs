@@ -1,28 +1,117 @@
 
 
-use std::collections::{HashMap, hash_map::{Iter as HashMapIter, IterMut as HashMapIterMut}};
+use std::collections::{HashMap, hash_map::{Entry, Iter as HashMapIter}};
 use std::marker::PhantomData;
 use std::iter::IntoIterator;
-
-use crate::basis::{Basis};
-use crate::coefficients::CoefficientField;
-use crate::vector::{Vector, VectorIteratorItem, VectorIteratorMutItem};
+use std::mem;
+use std::ops::RangeBounds;
+
+use crate::DegreeType;
+use crate::basis::{Basis, BasisWithDegree, OrderedBasis};
+use crate::coefficients::{ApproxEq, CoefficientField, CoefficientRing, Magnitude, ZeroPolicy};
+use crate::vector::{MapCoefficients, SimpleDenseVector, SparseVector, Vector, VectorApproxEq, VectorIteratorItem, VectorWithDegree};
+use crate::vector::DegreeProjection;
+use crate::vector::{GradedNormedVector, Norm, NormedVector};
 use std::borrow::{BorrowMut, Borrow};
 use std::hash::Hash;
 
 
 
+/// Sparse vector storing the non-zero coefficients in a hash map.
+///
+/// Arithmetic drops any coefficient that its `ZeroPolicy` considers to be zero. The default
+/// policy only drops exact zeros; a tolerance policy can be set so that floating point rounding
+/// errors do not fill the vector with tiny coefficients.
 #[derive(Debug)]
-pub struct SimpleSparseVector<'a, B, S, K>(HashMap<K, S>, PhantomData<&'a B>)
+pub struct SimpleSparseVector<'a, B, S, K>(HashMap<K, S>, ZeroPolicy<S>, PhantomData<&'a B>)
+    where B: Basis<KeyType=K>,
+          K: Hash + Eq + Clone,
+          S: CoefficientRing;
+
+
+impl<'a, B, S, K> SimpleSparseVector<'a, B, S, K>
+    where B: Basis<KeyType=K>,
+          K: Hash + Eq + Clone,
+          S: CoefficientRing
+{
+
+    pub fn with_zero_policy(mut self, policy: ZeroPolicy<S>) -> Self
+    {
+        self.set_zero_policy(policy);
+        self
+    }
+
+    /// Set the policy used to drop zero coefficients, and prune the vector under the new policy.
+    pub fn set_zero_policy(&mut self, policy: ZeroPolicy<S>)
+    {
+        self.1 = policy;
+        self.prune();
+    }
+
+    pub fn zero_policy(&self) -> &ZeroPolicy<S>
+    {
+        &self.1
+    }
+
+    /// Remove all coefficients that are zero under the zero policy of this vector.
+    pub fn prune(&mut self)
+    {
+        let policy = self.1;
+        self.0.retain(|_, v| !policy.is_zero(v));
+    }
+
+    /// Number of stored coefficients.
+    pub fn len(&self) -> usize
+    {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.0.is_empty()
+    }
+
+    /// Iterator over the stored key-value pairs, in no particular order.
+    pub fn iter(&self) -> HashMapIter<'_, K, S>
+    {
+        self.0.iter()
+    }
+
+    /// Add `val` to the coefficient of `key`, removing it if the result is zero.
+    fn add_to_key(&mut self, key: K, val: &S)
+    {
+        match self.0.entry(key) {
+            Entry::Occupied(mut entry) => {
+                if self.1.is_zero(entry.get_mut().add_inplace(val)) {
+                    entry.remove();
+                }
+            },
+            Entry::Vacant(entry) => {
+                if !self.1.is_zero(val) {
+                    entry.insert(val.clone());
+                }
+            }
+        }
+    }
+}
+
+
+impl<'a, B, S, K> Clone for SimpleSparseVector<'a, B, S, K>
     where B: Basis<KeyType=K>,
           K: Hash + Eq + Clone,
-          S: CoefficientField;
+          S: CoefficientRing
+{
+    fn clone(&self) -> Self
+    {
+        Self(self.0.clone(), self.1, PhantomData)
+    }
+}
 
 
 impl<'a, B, S, K> PartialEq for SimpleSparseVector<'a, B, S, K>
     where B: Basis<KeyType=K>,
           K: Hash + Eq + Clone,
-          S: CoefficientField
+          S: CoefficientRing
 {
     fn eq(&self, other: &Self) -> bool {
         if self.0.len() != other.0.len() {
@@ -61,7 +150,7 @@ impl<'vec, K: 'vec, S: 'vec> VectorIteratorItem<'vec, K, S> for (&'vec K, &'vec
 impl<'vec, 'a: 'vec, B: 'vec, S: 'vec, K: 'vec> IntoIterator for &'vec SimpleSparseVector<'a, B, S, K>
     where B: Basis<KeyType=K>,
           K: 'a + Hash + Eq + Clone,
-          S: 'a + CoefficientField
+          S: 'a + CoefficientRing
 {
     type Item = (&'vec K, &'vec S);
     type IntoIter = HashMapIter<'vec, K, S>;
@@ -77,42 +166,44 @@ impl<'vec, 'a: 'vec, B: 'vec, S: 'vec, K: 'vec> IntoIterator for &'vec SimpleSpa
 impl<'vec, 'a: 'vec, B, S, K> Vector<'vec> for SimpleSparseVector<'a, B, S, K>
     where B: 'static + Basis<KeyType=K>,
           K: 'static + Hash + Eq + Clone,
-          S: 'static + CoefficientField
+          S: 'static + CoefficientRing
 {
     type BasisType = B;
     type KeyType = B::KeyType;
     type ScalarType = S;
-    type RationalType = S::RationalType;
 
     fn new() -> Self {
-        Self(HashMap::new(), PhantomData)
+        Self(HashMap::new(), ZeroPolicy::exact(), PhantomData)
     }
 
     fn from_key(key: impl Into<Self::KeyType>) -> Self {
-        Self::from_key_scalar(key, S::ONE)
+        Self::from_key_scalar(key, S::one())
     }
 
     fn from_key_scalar(key: impl Into<Self::KeyType>, scalar: impl Into<Self::ScalarType>) -> Self {
-        let mut inner = HashMap::new();
-        inner.insert(key.into(), scalar.into());
-        Self(inner, PhantomData)
+        let mut result = Self::new();
+        result.insert_single(&key.into(), scalar);
+        result
     }
 
+    // As for dense vectors, the values of repeated keys are added together.
     fn from_iterator(iterator: impl IntoIterator<Item=(Self::KeyType, Self::ScalarType)>) -> Self {
-        let mut inner = HashMap::<K, S>::new();
+        let mut result = Self::new();
 
         for (key, val) in iterator.into_iter() {
-            inner.insert(key, val);
+            result.add_to_key(key, &val);
         }
-        SimpleSparseVector::<B, S, K>(inner, PhantomData)
+        result
     }
 
-    fn swap(&mut self, other: impl BorrowMut<Self>) {
-        todo!()
+    fn swap(&mut self, mut other: impl BorrowMut<Self>) {
+        let other = other.borrow_mut();
+        mem::swap(&mut self.0, &mut other.0);
+        mem::swap(&mut self.1, &mut other.1);
     }
 
     fn to_owned(&self) -> Self {
-        todo!()
+        self.clone()
     }
 
     fn clear(&mut self) {
@@ -128,33 +219,32 @@ impl<'vec, 'a: 'vec, B, S, K> Vector<'vec> for SimpleSparseVector<'a, B, S, K>
     }
 
     fn insert_single(&mut self, key: &Self::KeyType, value: impl Into<Self::ScalarType>) {
-        self.0.insert(key.clone(), value.into());
+        let val = value.into();
+        if self.1.is_zero(&val) {
+            self.0.remove(key);
+        } else {
+            self.0.insert(key.clone(), val);
+        }
     }
 
     fn insert(&mut self, iterator: impl IntoIterator<Item=(Self::KeyType, Self::ScalarType)>) {
-        todo!()
+        for (key, val) in iterator.into_iter() {
+            self.insert_single(&key, val);
+        }
     }
 
     fn erase(&mut self, key: impl Borrow<Self::KeyType>) {
-        todo!()
+        self.0.remove(key.borrow());
     }
 
     fn uminus_inplace(&mut self) -> &mut Self {
-        self.0.iter_mut().for_each(|(k, v)| { *v = S::uminus(v); });
+        self.0.iter_mut().for_each(|(_, v)| { *v = S::uminus(v); });
         self
     }
 
     fn add_inplace(&mut self, other: impl Borrow<Self>) -> &mut Self {
         for (k, v) in other.borrow().0.iter() {
-            match self.0.get_mut(k) {
-                Some(i) => {
-                    let r = S::add_inplace(i, v);
-                    if *r == S::ZERO {
-                        self.0.remove(k);
-                    }
-                },
-                None => { self.0.insert(k.clone(), v.clone()); }
-            };
+            self.add_to_key(k.clone(), v);
         }
         self
     }
@@ -164,11 +254,11 @@ impl<'vec, 'a: 'vec, B, S, K> Vector<'vec> for SimpleSparseVector<'a, B, S, K>
             match self.0.get_mut(k) {
                 Some(i) => {
                     let r = S::sub_inplace(i, v);
-                    if *r == S::ZERO {
+                    if self.1.is_zero(r) {
                         self.0.remove(k);
                     }
                 },
-                None => { self.0.insert(k.clone(), S::uminus(v)); }
+                None => self.add_to_key(k.clone(), &v.uminus())
             };
         }
         self
@@ -176,7 +266,22 @@ impl<'vec, 'a: 'vec, B, S, K> Vector<'vec> for SimpleSparseVector<'a, B, S, K>
 
     fn scalar_lmultiply_inplace(&mut self, scalar: impl Into<Self::ScalarType>) -> &mut Self {
         let s = scalar.into();
-        if s == S::ZERO {
+        if s == S::zero() {
+            self.0.clear();
+            return self;
+        }
+
+        self.0.iter_mut().for_each(move |(_, v)| {
+            S::lmul_inplace(v, &s);
+        });
+        self.prune();
+
+        self
+    }
+
+    fn scalar_rmultiply_inplace(&mut self, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        let s = scalar.into();
+        if s == S::zero() {
             self.0.clear();
             return self;
         }
@@ -184,25 +289,199 @@ impl<'vec, 'a: 'vec, B, S, K> Vector<'vec> for SimpleSparseVector<'a, B, S, K>
         self.0.iter_mut().for_each(move |(_, v)| {
             S::mul_inplace(v, &s);
         });
+        self.prune();
 
         self
     }
 
-    fn scalar_rdivide_inplace(&mut self, rational: impl Into<Self::RationalType>) -> &mut Self {
-        let r = rational.into();
+    fn add_scalar_rmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        let s = scalar.into();
+        for (k, v) in other.borrow().0.iter() {
+            self.add_to_key(k.clone(), &v.mul(&s));
+        }
+        self
+    }
+
+    fn add_scalar_lmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self {
+        let s = scalar.into();
+        for (k, v) in other.borrow().0.iter() {
+            self.add_to_key(k.clone(), &s.mul(v));
+        }
+        self
+    }
 
+    fn scalar_rdivide_inplace_impl(&mut self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
         self.0.iter_mut().for_each(move |(_, v)| {
-            S::div_inplace(v, &r);
+            S::div_inplace(v, rational);
         });
+        self.prune();
+
+        self
+    }
 
+    fn add_scalar_rdivide_impl(&mut self, other: &Self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        for (k, v) in other.0.iter() {
+            self.add_to_key(k.clone(), &v.div(rational));
+        }
+        self
+    }
+
+    fn sub_scalar_rdivide_impl(&mut self, other: &Self, rational: &S::RationalType) -> &mut Self
+        where S: CoefficientField
+    {
+        for (k, v) in other.0.iter() {
+            self.add_to_key(k.clone(), &v.div(rational).uminus());
+        }
         self
     }
+
+}
+
+
+impl<'vec, 'a: 'vec, B, S, K> SparseVector<'vec> for SimpleSparseVector<'a, B, S, K>
+    where B: 'static + Basis<KeyType=K>,
+          K: 'static + Hash + Eq + Clone,
+          S: 'static + CoefficientRing
+{}
+
+
+impl<'vec, 'a: 'vec, B, S, K> VectorApproxEq<'vec> for SimpleSparseVector<'a, B, S, K>
+    where B: 'static + Basis<KeyType=K>,
+          K: 'static + Hash + Eq + Clone,
+          S: 'static + ApproxEq
+{
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        let zero = S::zero();
+
+        self.0.iter().all(|(k, v)| v.approx_eq(other.0.get(k).unwrap_or(&zero), tol))
+            && other.0.iter()
+                .filter(|(k, _)| !self.0.contains_key(k))
+                .all(|(_, v)| v.is_approx_zero(tol))
+    }
+}
+
+
+impl<'vec, 'a: 'vec, B, S, K> VectorWithDegree<'vec> for SimpleSparseVector<'a, B, S, K>
+    where B: 'static + BasisWithDegree<KeyType=K>,
+          K: 'static + Hash + Eq + Clone,
+          S: 'static + CoefficientRing
+{
+    fn degree(&self) -> DegreeType {
+        self.0.keys().map(B::degree).max().unwrap_or(0)
+    }
+}
+
+
+impl<'vec, 'a: 'vec, B, S, K> DegreeProjection<'vec> for SimpleSparseVector<'a, B, S, K>
+    where B: 'static + BasisWithDegree<KeyType=K>,
+          K: 'static + Hash + Eq + Clone,
+          S: 'static + CoefficientRing
+{
+    fn project(&self, degrees: impl RangeBounds<DegreeType>) -> Self {
+        SimpleSparseVector(
+            self.0.iter()
+                .filter(|(k, _)| degrees.contains(&B::degree(k)))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            self.1,
+            PhantomData
+        )
+    }
+
+    fn truncate(&mut self, degree: DegreeType) {
+        self.0.retain(|k, _| B::degree(k) <= degree);
+    }
+}
+
+
+impl<'vec, 'a: 'vec, B, S, K> NormedVector<'vec> for SimpleSparseVector<'a, B, S, K>
+    where B: 'static + Basis<KeyType=K>,
+          K: 'static + Hash + Eq + Clone,
+          S: 'static + Magnitude
+{
+    fn norm(&self, norm: Norm) -> f64 {
+        norm.of_coefficients(self.0.values())
+    }
+
+    fn inner_product(&self, other: &Self) -> S {
+        let mut result = S::zero();
+        for (k, v) in self.0.iter() {
+            if let Some(other_v) = other.0.get(k) {
+                result.add_inplace(&v.mul(other_v));
+            }
+        }
+        result
+    }
+}
+
+impl<'vec, 'a: 'vec, B, S, K> GradedNormedVector<'vec> for SimpleSparseVector<'a, B, S, K>
+    where B: 'static + BasisWithDegree<KeyType=K>,
+          K: 'static + Hash + Eq + Clone,
+          S: 'static + Magnitude
+{
+    fn degree_norm(&self, degree: DegreeType, norm: Norm) -> f64 {
+        norm.of_coefficients(self.0.iter().filter(|(k, _)| B::degree(k) == degree).map(|(_, v)| v))
+    }
+}
+
+
+impl<'vec, 'a: 'vec, B, S, T, K> MapCoefficients<'vec, T> for SimpleSparseVector<'a, B, S, K>
+    where B: 'static + Basis<KeyType=K>,
+          K: 'static + Hash + Eq + Clone,
+          S: 'static + CoefficientRing,
+          T: 'static + CoefficientRing
+{
+    type Output = SimpleSparseVector<'static, B, T, K>;
+
+    fn map_coefficients(&self, mut func: impl FnMut(&S) -> T) -> Self::Output {
+        let mut result = SimpleSparseVector(
+            self.0.iter().map(|(k, v)| (k.clone(), func(v))).collect(),
+            ZeroPolicy::exact(),
+            PhantomData
+        );
+        // Coefficients can become zero in the conversion, for instance when rounding.
+        result.prune();
+        result
+    }
+}
+
+
+// Zero coefficients of the dense vector are not stored.
+impl<'a, 'b, B, S, K> From<SimpleDenseVector<'b, B, S>> for SimpleSparseVector<'a, B, S, K>
+    where B: OrderedBasis<KeyType=K>,
+          K: Hash + Eq + Clone,
+          S: CoefficientRing
+{
+    fn from(arg: SimpleDenseVector<'b, B, S>) -> Self {
+        let zero = S::zero();
+        let inner = arg.as_slice().iter()
+            .enumerate()
+            .filter(|(_, v)| **v != zero)
+            .map(|(i, v)| (B::index_to_key(i), v.clone()))
+            .collect();
+
+        Self(inner, ZeroPolicy::exact(), PhantomData)
+    }
+}
+
+impl<'a, 'b, B, S, K> From<SimpleSparseVector<'b, B, S, K>> for SimpleDenseVector<'a, B, S>
+    where B: 'static + OrderedBasis<KeyType=K>,
+          K: Hash + Eq + Clone,
+          S: 'static + CoefficientRing
+{
+    fn from(arg: SimpleSparseVector<'b, B, S, K>) -> Self {
+        <Self as Vector>::from_iterator(arg.0)
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
-/*
+
     use super::*;
     use crate::free_tensor::{TensorBasis, TensorKey};
 
@@ -211,15 +490,184 @@ mod tests {
 
     type Vect<'a> = SimpleSparseVector<'a, TBasis, f64, TKey>;
 
+    #[test]
+    fn test_tolerance_policy_prunes_rounding_errors() {
+        let lhs = Vect::from_iterator(vec![(TKey::from_letter(1), 0.1 + 0.2), (TKey::from_letter(2), 1.0)]);
+        let rhs = Vect::from_iterator(vec![(TKey::from_letter(1), 0.3)]);
+
+        let mut exact = Vect::new();
+        exact.add_inplace(&lhs).sub_inplace(&rhs);
+        assert_eq!(exact.len(), 2);
+
+        let mut approx = Vect::new().with_zero_policy(ZeroPolicy::default_tolerance());
+        approx.add_inplace(&lhs).sub_inplace(&rhs);
+        assert_eq!(approx.len(), 1);
+        assert_eq!(approx.get(TKey::from_letter(2)), Some(&1.0));
+    }
+
+    #[test]
+    fn test_set_zero_policy_prunes_existing() {
+        let mut vec = Vect::from_iterator(vec![(TKey::new(), 1e-17), (TKey::from_letter(3), -2.0)]);
+
+        vec.set_zero_policy(ZeroPolicy::with_tolerance(1e-15));
+
+        assert_eq!(vec.len(), 1);
+        assert_eq!(vec.get(TKey::new()), None);
+    }
+
+    #[test]
+    fn test_zero_coefficients_are_not_stored() {
+        assert!(Vect::from_key_scalar(TKey::from_letter(1), 0.0).is_empty());
+
+        let mut vec = Vect::new().with_zero_policy(ZeroPolicy::with_tolerance(1e-15));
+        vec.sub_inplace(Vect::from_iterator(vec![(TKey::new(), 1e-17), (TKey::from_letter(2), 1.0)]));
+
+        assert_eq!(vec.len(), 1);
+        assert_eq!(vec.get(TKey::from_letter(2)), Some(&-1.0));
+    }
+
+    #[test]
+    fn test_approx_eq_treats_missing_as_zero() {
+        let lhs = Vect::from_iterator(vec![(TKey::new(), 1.0), (TKey::from_letter(1), 1e-14)]);
+        let rhs = Vect::from_iterator(vec![(TKey::new(), 1.0 + 1e-14)]);
+
+        assert_ne!(lhs, rhs);
+        assert!(lhs.approx_eq_default(&rhs));
+        assert!(rhs.approx_eq_default(&lhs));
+        assert!(!lhs.approx_eq(&rhs, 1e-16));
+    }
+
+    #[test]
+    fn test_convert_coefficients_drops_new_zeros() {
+        let vec = Vect::from_iterator(vec![(TKey::new(), 1.5), (TKey::from_letter(2), 1e-60)]);
+
+        let result: SimpleSparseVector<TBasis, f32, TKey> = vec.convert_coefficients();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.get(TKey::new()), Some(&1.5f32));
+    }
 
     #[test]
     fn test_add_inplace() {
+        let mut lhs = Vect::from_iterator(vec![(TKey::new(), 1.0), (TKey::from_letter(1), 2.0)]);
+        let rhs = Vect::from_iterator(vec![(TKey::from_letter(1), -2.0), (TKey::from_letter(2), 3.0)]);
 
+        lhs.add_inplace(&rhs);
 
+        let expected = Vect::from_iterator(vec![(TKey::new(), 1.0), (TKey::from_letter(2), 3.0)]);
+        assert_eq!(lhs, expected);
+        assert_eq!(lhs.len(), 2);
+    }
+
+    #[test]
+    fn test_default_binary_operations() {
+        let lhs = Vect::from_key(TKey::from_letter(1));
+        let rhs = Vect::from_key_scalar(TKey::from_letter(2), 2.0);
+
+        let sum = lhs.add(&rhs);
+        assert_eq!(sum.get(TKey::from_letter(1)), Some(&1.0));
+        assert_eq!(sum.get(TKey::from_letter(2)), Some(&2.0));
+
+        let neg = sum.uminus();
+        assert_eq!(neg.get(TKey::from_letter(2)), Some(&-2.0));
+        assert!(neg.add(&sum).is_empty());
+        assert_eq!(sum.sub(&rhs), lhs);
+    }
+
+    #[test]
+    fn test_from_iterator_adds_repeated_keys() {
+        let vec = Vect::from_iterator(vec![
+            (TKey::from_letter(1), 1.0),
+            (TKey::from_letter(1), 2.0),
+            (TKey::from_letter(2), 1.0),
+            (TKey::from_letter(2), -1.0),
+        ]);
+
+        assert_eq!(vec.len(), 1);
+        assert_eq!(vec.get(TKey::from_letter(1)), Some(&3.0));
+    }
+
+    #[test]
+    fn test_insert_and_erase() {
+        let mut vec = Vect::from_key(TKey::new());
+
+        vec.insert(vec![(TKey::from_letter(1), 2.0), (TKey::new(), 0.0)]);
+        assert_eq!(vec.len(), 1);
+        assert_eq!(vec.get(TKey::from_letter(1)), Some(&2.0));
+
+        vec.erase(TKey::from_letter(1));
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut lhs = Vect::from_key(TKey::new());
+        let mut rhs = Vect::from_key(TKey::from_letter(3))
+            .with_zero_policy(ZeroPolicy::with_tolerance(1e-10));
+
+        lhs.swap(&mut rhs);
+
+        assert_eq!(lhs, Vect::from_key(TKey::from_letter(3)));
+        assert_eq!(lhs.zero_policy().tolerance(), 1e-10);
+        assert_eq!(rhs, Vect::from_key(TKey::new()));
+    }
+
+    #[test]
+    fn test_dense_sparse_round_trip() {
+        let sparse = Vect::from_iterator(vec![(TKey::new(), 1.0), (TKey::from_letters(&[2, 3]), -2.0)]);
+
+        let dense: SimpleDenseVector<TBasis, f64> = sparse.clone().into();
+        assert_eq!(dense.get(TKey::from_letters(&[2, 3])), Some(&-2.0));
+        assert_eq!(dense.get(TKey::from_letter(1)), Some(&0.0));
+
+        let back: Vect = dense.into();
+        assert_eq!(back, sparse);
+    }
+
+    #[test]
+    fn test_add_dense_to_sparse() {
+        use crate::vector::CrossTypeVectorExt;
 
+        let mut sparse = Vect::from_iterator(vec![(TKey::from_letter(1), 1.0)]);
+        let dense = SimpleDenseVector::<TBasis, f64>::from_iterator(vec![(TKey::from_letter(1), -1.0), (TKey::from_letter(2), 3.0)]);
+
+        CrossTypeVectorExt::add_inplace(&mut sparse, &dense);
+
+        assert_eq!(sparse.get(TKey::from_letter(1)), None);
+        assert_eq!(sparse.get(TKey::from_letter(2)), Some(&3.0));
+        assert_eq!(sparse.get(TKey::new()), None);
+    }
+
+    #[test]
+    fn test_add_sparse_to_dense_accumulator() {
+        use crate::vector::CrossTypeVectorExt;
+
+        let mut acc = SimpleDenseVector::<TBasis, f64>::from_iterator(vec![(TKey::from_letter(1), 1.0)]);
+        let increment = Vect::from_iterator(vec![(TKey::from_letter(1), 2.0), (TKey::from_letters(&[1, 2]), 1.0)]);
+
+        CrossTypeVectorExt::add_inplace(&mut acc, &increment);
+        CrossTypeVectorExt::sub_scalar_mul(&mut acc, &increment, 0.5);
+
+        assert_eq!(acc.get(TKey::from_letter(1)), Some(&2.0));
+        assert_eq!(acc.get(TKey::from_letters(&[1, 2])), Some(&0.5));
     }
 
+    #[test]
+    fn test_cross_type_scalars_multiply_on_the_right() {
+        use crate::coefficients::{Matrix, Rational};
+        use crate::vector::CrossTypeVectorExt;
+
+        type Mat = Matrix<Rational, 2>;
+        let a = Mat::from_fn(|i, j| Rational::from((i == 0 && j == 1) as i8));
+        let b = Mat::from_fn(|i, j| Rational::from((i == 1 && j == 0) as i8));
 
+        let dense = SimpleDenseVector::<TBasis, Mat>::from_key_scalar(TKey::from_letter(1), a.clone());
+        let mut sparse = SimpleSparseVector::<TBasis, Mat, TKey>::new();
+        CrossTypeVectorExt::add_scalar_mul(&mut sparse, &dense, b.clone());
+
+        assert_eq!(sparse.get(TKey::from_letter(1)), Some(&a.mul(&b)));
+        assert_ne!(a.mul(&b), b.mul(&a));
+    }
 
     #[test]
     fn test_iterator()
@@ -230,23 +678,53 @@ mod tests {
             (TKey::from_letter(2), 3.0)
         ]);
 
-        let mut itr = vec.into_iter();
+        // Hash map iteration order is unspecified, so sort the items by value
+        let mut items: Vec<(TKey, f64)> = (&vec).into_iter()
+            .map(|itm| (itm.key().clone(), *itm.value()))
+            .collect();
+        items.sort_by(|l, r| l.1.partial_cmp(&r.1).unwrap());
+
+        assert_eq!(items, vec![
+            (TKey::new(), 1.0),
+            (TKey::from_letter(1), 2.0),
+            (TKey::from_letter(2), 3.0)
+        ]);
+        assert_eq!(vec.iter().count(), 3);
+    }
+
+    #[test]
+    fn test_norms_match_dense() {
+        let sparse = Vect::from_iterator(vec![
+            (TKey::new(), 1.0),
+            (TKey::from_letter(2), -2.0),
+            (TKey::from_letters(&[1, 3]), 2.0)
+        ]);
+        let dense: SimpleDenseVector<TBasis, f64> = sparse.clone().into();
 
-        let itm = itr.next().unwrap();
-        assert_eq!(itm.key(), TKey::new());
-        assert_eq!(itm.value(), 1.0);
+        for norm in [Norm::L1, Norm::L2, Norm::LInf] {
+            assert_eq!(sparse.norm(norm), dense.norm(norm));
+            assert_eq!(sparse.degree_norms(norm), dense.degree_norms(norm));
+        }
+        assert_eq!(sparse.l2_norm(), 3.0);
+        assert_eq!(sparse.degree_norms(Norm::L1), vec![1.0, 2.0, 2.0]);
 
-        let itm = itr.next().unwrap();
-        assert_eq!(itm.key(), TKey::from_letter(1));
-        assert_eq!(itm.value(), 2.0);
+        let other = Vect::from_iterator(vec![(TKey::from_letter(2), 3.0), (TKey::from_letter(1), 5.0)]);
+        assert_eq!(sparse.inner_product(&other), -6.0);
+    }
 
-        let itm = itr.next().unwrap();
-        assert_eq!(itm.key(), TKey::from_letter(2));
-        assert_eq!(itm.value(), 3.0);
+    #[test]
+    fn test_project_and_truncate() {
+        let mut vec = Vect::from_iterator(vec![
+            (TKey::new(), 1.0),
+            (TKey::from_letter(2), -2.0),
+            (TKey::from_letters(&[1, 3]), 2.0)
+        ]);
 
-        assert_eq!(itr.next(), None);
+        assert_eq!(vec.project(1..), Vect::from_iterator(vec![(TKey::from_letter(2), -2.0), (TKey::from_letters(&[1, 3]), 2.0)]));
+        assert_eq!(vec.project(3..), Vect::new());
 
+        vec.truncate(0);
+        assert_eq!(vec, Vect::from_key(TKey::new()));
     }
-*/
 
-}
\ No newline at end of file
+}
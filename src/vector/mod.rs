@@ -16,6 +16,10 @@
 //! numerous combinations simultaneously. The second reason is to avoid ambiguity. It is possible
 //! that we may wish to implement several different vector structures on a data container, and
 //! implementing the built-in traits would make this tricky.
+//! That said, the vector types provided by this library do implement the built-in operators as a
+//! convenience, each one forwarding to the corresponding method (see the `ops` module). These
+//! operators evaluate eagerly; the `expression` module provides lazy linear combinations that
+//! are evaluated without temporaries.
 //!
 //! The `Vector` trait expresses it's connection to the basis type and scalar coefficient field by
 //! means of the associated types `BasisType` and `ScalarFieldType`. To make accessing the numerous
@@ -32,20 +36,19 @@
 //! `Vector` trait). You may wish to implement an optimised implementation.
 
 
-use std::iter::IntoIterator;
-
 
+pub use expression::{lazy, LinearCombination};
 pub use implementation::SimpleDenseVector;
 pub use traits::*;
-//pub use wrapper::VectorWrapper;
 
 
 
 
 
+pub mod expression;
 pub mod implementation;
+pub mod ops;
 pub mod traits;
-//pub mod wrapper;
 
 
 
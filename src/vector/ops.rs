@@ -0,0 +1,331 @@
+//! Arithmetic operators for the vector and algebra types.
+//!
+//! The operators are thin wrappers around the methods of `Vector` and `Algebra`. Operators that
+//! take a vector by value reuse its storage for the result, so `a + &b` does not allocate when
+//! `a` is no longer needed. Scalars multiply vectors on the right, so `v * s` is
+//! `v.scalar_rmultiply(s)`, which matters for non-commutative coefficients. Division is by
+//! elements of the rational type of the coefficient field.
+//!
+//! The standard operator traits are foreign, so they cannot be implemented for every type
+//! implementing `Vector`. Instead, the operators are implemented for each concrete vector type
+//! provided by the library, and for the tensor types that implement `Algebra`. Vector types
+//! defined outside the library have the methods but not the operators.
+//!
+//! Within the crate, the implementations are generated by macros, given the generic parameters
+//! of a type, its scalar type and the bounds that make it a vector.
+
+use crate::basis::{OrderedBasis, OrderedBasisWithDegree, Basis};
+use crate::coefficients::CoefficientRing;
+use crate::vector::implementation::{HybridVector, OrderedSparseVector, SimpleSparseVector};
+use crate::vector::SimpleDenseVector;
+use crate::DegreeType;
+
+use std::hash::Hash;
+
+
+/// Implement `+`, `-`, unary `-`, scalar `*` and `/`, and the compound assignment operators for
+/// a vector type.
+///
+/// The generic parameters of the type are given in the first brackets, and the bounds under
+/// which it implements `Vector` in the last.
+macro_rules! impl_vector_ops {
+    ([$($gen:tt)*] $ty:ty, $scalar:ty, [$($bounds:tt)*]) => {
+
+        impl<$($gen)*, Rhs: std::borrow::Borrow<$ty>> std::ops::Add<Rhs> for $ty
+            where $($bounds)*
+        {
+            type Output = $ty;
+
+            fn add(mut self, rhs: Rhs) -> $ty {
+                crate::vector::Vector::add_inplace(&mut self, rhs.borrow());
+                self
+            }
+        }
+
+        impl<'r, $($gen)*, Rhs: std::borrow::Borrow<$ty>> std::ops::Add<Rhs> for &'r $ty
+            where $($bounds)*
+        {
+            type Output = $ty;
+
+            fn add(self, rhs: Rhs) -> $ty {
+                crate::vector::Vector::add(self, rhs.borrow())
+            }
+        }
+
+        impl<$($gen)*, Rhs: std::borrow::Borrow<$ty>> std::ops::Sub<Rhs> for $ty
+            where $($bounds)*
+        {
+            type Output = $ty;
+
+            fn sub(mut self, rhs: Rhs) -> $ty {
+                crate::vector::Vector::sub_inplace(&mut self, rhs.borrow());
+                self
+            }
+        }
+
+        impl<'r, $($gen)*, Rhs: std::borrow::Borrow<$ty>> std::ops::Sub<Rhs> for &'r $ty
+            where $($bounds)*
+        {
+            type Output = $ty;
+
+            fn sub(self, rhs: Rhs) -> $ty {
+                crate::vector::Vector::sub(self, rhs.borrow())
+            }
+        }
+
+        impl<$($gen)*> std::ops::Neg for $ty
+            where $($bounds)*
+        {
+            type Output = $ty;
+
+            fn neg(mut self) -> $ty {
+                crate::vector::Vector::uminus_inplace(&mut self);
+                self
+            }
+        }
+
+        impl<'r, $($gen)*> std::ops::Neg for &'r $ty
+            where $($bounds)*
+        {
+            type Output = $ty;
+
+            fn neg(self) -> $ty {
+                crate::vector::Vector::uminus(self)
+            }
+        }
+
+        impl<$($gen)*> std::ops::Mul<$scalar> for $ty
+            where $($bounds)*
+        {
+            type Output = $ty;
+
+            fn mul(mut self, rhs: $scalar) -> $ty {
+                crate::vector::Vector::scalar_rmultiply_inplace(&mut self, rhs);
+                self
+            }
+        }
+
+        impl<'r, $($gen)*> std::ops::Mul<$scalar> for &'r $ty
+            where $($bounds)*
+        {
+            type Output = $ty;
+
+            fn mul(self, rhs: $scalar) -> $ty {
+                crate::vector::Vector::scalar_rmultiply(self, rhs)
+            }
+        }
+
+        impl<$($gen)*, Q> std::ops::Div<Q> for $ty
+            where $($bounds)*,
+                  $scalar: crate::coefficients::CoefficientField,
+                  Q: Into<<$scalar as crate::coefficients::CoefficientField>::RationalType>
+        {
+            type Output = $ty;
+
+            fn div(mut self, rhs: Q) -> $ty {
+                crate::vector::Vector::scalar_rdivide_inplace(&mut self, rhs);
+                self
+            }
+        }
+
+        impl<'r, $($gen)*, Q> std::ops::Div<Q> for &'r $ty
+            where $($bounds)*,
+                  $scalar: crate::coefficients::CoefficientField,
+                  Q: Into<<$scalar as crate::coefficients::CoefficientField>::RationalType>
+        {
+            type Output = $ty;
+
+            fn div(self, rhs: Q) -> $ty {
+                crate::vector::Vector::scalar_rdivide(self, rhs)
+            }
+        }
+
+        impl<$($gen)*, Rhs: std::borrow::Borrow<$ty>> std::ops::AddAssign<Rhs> for $ty
+            where $($bounds)*
+        {
+            fn add_assign(&mut self, rhs: Rhs) {
+                crate::vector::Vector::add_inplace(self, rhs.borrow());
+            }
+        }
+
+        impl<$($gen)*, Rhs: std::borrow::Borrow<$ty>> std::ops::SubAssign<Rhs> for $ty
+            where $($bounds)*
+        {
+            fn sub_assign(&mut self, rhs: Rhs) {
+                crate::vector::Vector::sub_inplace(self, rhs.borrow());
+            }
+        }
+
+        impl<$($gen)*> std::ops::MulAssign<$scalar> for $ty
+            where $($bounds)*
+        {
+            fn mul_assign(&mut self, rhs: $scalar) {
+                crate::vector::Vector::scalar_rmultiply_inplace(self, rhs);
+            }
+        }
+
+        impl<$($gen)*, Q> std::ops::DivAssign<Q> for $ty
+            where $($bounds)*,
+                  $scalar: crate::coefficients::CoefficientField,
+                  Q: Into<<$scalar as crate::coefficients::CoefficientField>::RationalType>
+        {
+            fn div_assign(&mut self, rhs: Q) {
+                crate::vector::Vector::scalar_rdivide_inplace(self, rhs);
+            }
+        }
+    };
+}
+
+
+/// Implement `*` and `*=` between elements of an algebra type as `Algebra::multiply` truncated
+/// at the given degree.
+macro_rules! impl_algebra_ops {
+    ([$($gen:tt)*] $ty:ty, $depth:expr, [$($bounds:tt)*]) => {
+
+        impl<$($gen)*> std::ops::Mul<$ty> for $ty
+            where $($bounds)*
+        {
+            type Output = $ty;
+
+            fn mul(self, rhs: $ty) -> $ty {
+                crate::algebra::Algebra::multiply(&self, &rhs, Some($depth))
+            }
+        }
+
+        impl<'r, $($gen)*> std::ops::Mul<&'r $ty> for $ty
+            where $($bounds)*
+        {
+            type Output = $ty;
+
+            fn mul(self, rhs: &'r $ty) -> $ty {
+                crate::algebra::Algebra::multiply(&self, rhs, Some($depth))
+            }
+        }
+
+        impl<'r, $($gen)*> std::ops::Mul<$ty> for &'r $ty
+            where $($bounds)*
+        {
+            type Output = $ty;
+
+            fn mul(self, rhs: $ty) -> $ty {
+                crate::algebra::Algebra::multiply(self, &rhs, Some($depth))
+            }
+        }
+
+        impl<'r, 's, $($gen)*> std::ops::Mul<&'s $ty> for &'r $ty
+            where $($bounds)*
+        {
+            type Output = $ty;
+
+            fn mul(self, rhs: &'s $ty) -> $ty {
+                crate::algebra::Algebra::multiply(self, rhs, Some($depth))
+            }
+        }
+
+        impl<$($gen)*> std::ops::MulAssign<$ty> for $ty
+            where $($bounds)*
+        {
+            fn mul_assign(&mut self, rhs: $ty) {
+                crate::algebra::Algebra::multiply_inplace(self, &rhs, Some($depth));
+            }
+        }
+
+        impl<'r, $($gen)*> std::ops::MulAssign<&'r $ty> for $ty
+            where $($bounds)*
+        {
+            fn mul_assign(&mut self, rhs: &'r $ty) {
+                crate::algebra::Algebra::multiply_inplace(self, rhs, Some($depth));
+            }
+        }
+    };
+}
+
+pub(crate) use impl_vector_ops;
+pub(crate) use impl_algebra_ops;
+
+
+impl_vector_ops!(['a, B, S] SimpleDenseVector<'a, B, S>, S,
+    [B: 'static + OrderedBasis, S: 'static + CoefficientRing]);
+
+impl_vector_ops!(['a, B, S, K] SimpleSparseVector<'a, B, S, K>, S,
+    [B: 'static + Basis<KeyType=K>, K: 'static + Hash + Eq + Clone, S: 'static + CoefficientRing]);
+
+impl_vector_ops!(['a, B, S, K] OrderedSparseVector<'a, B, S, K>, S,
+    [B: 'static + OrderedBasis<KeyType=K>, K: 'static + Clone, S: 'static + CoefficientRing]);
+
+impl_vector_ops!(['a, B, S, const DENSE_DEGREE: DegreeType] HybridVector<'a, B, S, DENSE_DEGREE>, S,
+    [B: 'static + OrderedBasisWithDegree, B::KeyType: 'static + Clone, S: 'static + CoefficientRing]);
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::coefficients::Rational;
+    use crate::free_tensor::{TensorBasis, TensorKey};
+    use crate::vector::Vector;
+
+    type TKey = TensorKey<2>;
+    type Vect<'a> = SimpleDenseVector<'a, TensorBasis<2>, f64>;
+
+    #[test]
+    fn test_add_vectors() {
+        let vec1 = Vect::from_iterator(vec![
+            (TKey::new(), 1.0),
+            (TKey::from_letter(1), 2.0),
+            (TKey::from_letter(2), 3.0)
+        ]);
+        let vec2 = Vect::from_iterator(vec![
+            (TKey::new(), -1.0),
+            (TKey::from_letter(1), -1.0),
+            (TKey::from_letter(2), -1.0)
+        ]);
+
+        let expected = Vect::from_iterator(vec![
+            (TKey::new(), 0.0),
+            (TKey::from_letter(1), 1.0),
+            (TKey::from_letter(2), 2.0)
+        ]);
+
+        assert_eq!(&vec1 + &vec2, expected);
+        assert_eq!(vec1 + vec2, expected);
+    }
+
+    #[test]
+    fn test_formula() {
+        let x = Vect::from_key(TKey::from_letter(1));
+        let y = Vect::from_key(TKey::from_letter(2));
+
+        let result = -(&x * 2.0 - &y) / 4i8;
+
+        let expected = Vect::from_iterator(vec![(TKey::from_letter(1), -0.5), (TKey::from_letter(2), 0.25)]);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_compound_assignment() {
+        let x = SimpleSparseVector::<TensorBasis<2>, Rational, TKey>::from_key(TKey::from_letter(1));
+        let y = SimpleSparseVector::<TensorBasis<2>, Rational, TKey>::from_key(TKey::from_letter(2));
+
+        let mut acc = x.clone();
+        acc += &y;
+        acc *= Rational::from(3i8);
+        acc -= &x;
+        acc /= 2i8;
+
+        assert_eq!(acc.get(TKey::from_letter(1)), Some(&Rational::from(1i8)));
+        assert_eq!(acc.get(TKey::from_letter(2)), Some(&Rational::new(3, 2)));
+    }
+
+    #[test]
+    fn test_ordered_and_hybrid_operators() {
+        let x = OrderedSparseVector::<TensorBasis<2>, Rational, TKey>::from_key(TKey::new());
+        let sum = &x + &x;
+        assert_eq!(sum.get(TKey::new()), Some(&Rational::from(2i8)));
+
+        let h = HybridVector::<TensorBasis<2>, f64, 0>::from_iterator(vec![(TKey::new(), 1.0), (TKey::from_letter(1), 1.0)]);
+        let diff = &h * 3.0 - h;
+        assert_eq!(diff.get(TKey::from_letter(1)), Some(&2.0));
+    }
+}
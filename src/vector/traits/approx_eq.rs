@@ -0,0 +1,22 @@
+
+use crate::coefficients::ApproxEq;
+
+use super::Vector;
+
+
+/// Comparison of vectors up to a tolerance on each coefficient.
+///
+/// Two vectors are approximately equal if each coefficient of one is within the tolerance of the
+/// corresponding coefficient of the other, where coefficients that are not stored count as zero.
+pub trait VectorApproxEq<'vec> : Vector<'vec>
+    where <Self as Vector<'vec>>::ScalarType: ApproxEq
+{
+
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool;
+
+    fn approx_eq_default(&self, other: &Self) -> bool
+    {
+        self.approx_eq(other, <Self::ScalarType as ApproxEq>::default_tolerance())
+    }
+
+}
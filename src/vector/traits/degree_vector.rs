@@ -1,4 +1,6 @@
 
+use std::ops::RangeBounds;
+
 use crate::DegreeType;
 use crate::basis::BasisWithDegree;
 
@@ -13,6 +15,20 @@ pub trait VectorWithDegree<'vec> : Vector<'vec>
 }
 
 
+/// Projection of a graded vector onto the components of some of its degrees.
+pub trait DegreeProjection<'vec> : VectorWithDegree<'vec>
+    where <Self as Vector<'vec>>::BasisType: BasisWithDegree
+{
+
+    /// Copy of the components of the vector with degree in `degrees`.
+    fn project(&self, degrees: impl RangeBounds<DegreeType>) -> Self;
+
+    /// Remove the components of the vector with degree above `degree`.
+    fn truncate(&mut self, degree: DegreeType);
+
+}
+
+
 
 /*
 impl<'a, V> VectorWithDegree<'a> for V
@@ -1,7 +1,7 @@
 use std::borrow::Borrow;
 
-use crate::vector::{Vector};
-use crate::coefficients::CoefficientField;
+use crate::vector::{Vector, VectorIteratorItem};
+use crate::coefficients::{CoefficientRing, CoefficientField};
 
 pub trait VectorKeyExt<'vec> : Vector<'vec> {
 
@@ -12,7 +12,7 @@ pub trait VectorKeyExt<'vec> : Vector<'vec> {
     ) -> &mut Self
     {
         if let Some(v) = self.get_mut(key.borrow()) {
-            <Self::ScalarType as CoefficientField>::add_inplace(v,  &val.into());
+            <Self::ScalarType as CoefficientRing>::add_inplace(v,  &val.into());
         } else {
             self.insert_single(key.borrow(), val.into());
         }
@@ -26,9 +26,9 @@ pub trait VectorKeyExt<'vec> : Vector<'vec> {
     ) -> &mut Self
     {
         if let Some(v) = self.get_mut(key.borrow()) {
-            <Self::ScalarType as CoefficientField>::sub_inplace(v, &val.into());
+            <Self::ScalarType as CoefficientRing>::sub_inplace(v, &val.into());
         } else {
-            self.insert_single(key.borrow(), <Self::ScalarType as CoefficientField>::uminus(&val.into()));
+            self.insert_single(key.borrow(), <Self::ScalarType as CoefficientRing>::uminus(&val.into()));
         }
         self
     }
@@ -38,10 +38,11 @@ pub trait VectorKeyExt<'vec> : Vector<'vec> {
         key: impl Borrow<Self::KeyType>,
         val: impl Into<<Self::ScalarType as CoefficientField>::RationalType>
     ) -> &mut Self
+        where Self::ScalarType: CoefficientField
     {
-        let sca = <Self::ScalarType as CoefficientField>::div(&Self::ScalarType::ONE, &val.into());
+        let sca = <Self::ScalarType as CoefficientField>::div(&Self::ScalarType::one(), &val.into());
         if let Some(v) = self.get_mut(key.borrow()) {
-            <Self::ScalarType as CoefficientField>::add_inplace(v, &sca);
+            <Self::ScalarType as CoefficientRing>::add_inplace(v, &sca);
         } else {
             self.insert_single(key.borrow().clone(), sca);
         }
@@ -54,10 +55,11 @@ pub trait VectorKeyExt<'vec> : Vector<'vec> {
         key: impl Borrow<Self::KeyType>,
         val: impl Into<<Self::ScalarType as CoefficientField>::RationalType>
     ) -> &mut Self
+        where Self::ScalarType: CoefficientField
     {
-        let sca = <Self::ScalarType as CoefficientField>::div(&Self::ScalarType::MONE, &val.into());
+        let sca = <Self::ScalarType as CoefficientField>::div(&Self::ScalarType::mone(), &val.into());
         if let Some(v) = self.get_mut(key.borrow()) {
-            <Self::ScalarType as CoefficientField>::sub_inplace(v, &sca);
+            <Self::ScalarType as CoefficientRing>::sub_inplace(v, &sca);
         } else {
             self.insert_single(key.borrow().clone(), sca);
         }
@@ -76,89 +78,80 @@ pub trait CrossTypeVectorExt<'vec1, 'vec2, V> : Vector<'vec1>
     where V: Vector<'vec2, BasisType=Self::BasisType, ScalarType=Self::ScalarType>
 {
 
-    fn add_inplace(&mut self, other: &V) -> &mut Self;
-    fn sub_inplace(&mut self, other: &V) -> &mut Self;
+    fn add_inplace(&mut self, other: &'vec2 V) -> &mut Self;
+    fn sub_inplace(&mut self, other: &'vec2 V) -> &mut Self;
 
-    fn add_scalar_mul(&mut self, other: &V, s: Self::ScalarType) -> &mut Self;
-    fn sub_scalar_mul(&mut self, other: &V, s: Self::ScalarType) -> &mut Self;
-    fn add_scalar_div(&mut self, other: &V, s: Self::RationalType) -> &mut Self;
-    fn sub_scalar_div(&mut self, other: &V, s: Self::RationalType) -> &mut Self;
+    fn add_scalar_mul(&mut self, other: &'vec2 V, s: Self::ScalarType) -> &mut Self;
+    fn sub_scalar_mul(&mut self, other: &'vec2 V, s: Self::ScalarType) -> &mut Self;
+    fn add_scalar_div(&mut self, other: &'vec2 V, s: <Self::ScalarType as CoefficientField>::RationalType) -> &mut Self
+        where Self::ScalarType: CoefficientField;
+    fn sub_scalar_div(&mut self, other: &'vec2 V, s: <Self::ScalarType as CoefficientField>::RationalType) -> &mut Self
+        where Self::ScalarType: CoefficientField;
 
 }
 
-/*
-impl<U, V> CrossTypeVectorExt<V> for U
-    where U: Vector,
-          V: Vector<BasisType=U::BasisType, ScalarFieldType=U::ScalarFieldType>,
+// Coefficients of other are added key by key, so this works for any pair of vector types over
+// the same basis, provided that references to other can be iterated over.
+impl<'vec1, 'vec2, U, V> CrossTypeVectorExt<'vec1, 'vec2, V> for U
+    where U: Vector<'vec1>,
+          V: Vector<'vec2, BasisType=U::BasisType, KeyType=U::KeyType, ScalarType=U::ScalarType>,
+          &'vec2 V: IntoIterator,
+          <&'vec2 V as IntoIterator>::Item: VectorIteratorItem<'vec2, U::KeyType, U::ScalarType>
 {
-    fn add_inplace(&mut self, other: &V) -> &mut Self {
-        for (k, v) in other.iter_item() {
-            if let Some(curr) = self.get_mut( k) {
-                ScalarField::<U>::add_inplace(curr, v);
-            } else {
-                self.insert_single(k, v.clone());
-            }
+    fn add_inplace(&mut self, other: &'vec2 V) -> &mut Self {
+        for item in other {
+            add_to_coefficient(self, item.key().borrow(), item.value().clone());
         }
         self
     }
 
-    fn sub_inplace(&mut self, other: &V) -> &mut Self {
-        for (k, v) in other.iter_item() {
-            if let Some(curr) = self.get_mut(k) {
-                ScalarField::<U>::sub_inplace(curr, v);
-            } else {
-                self.insert_single(k, ScalarField::<U>::uminus(v));
-            }
+    fn sub_inplace(&mut self, other: &'vec2 V) -> &mut Self {
+        for item in other {
+            add_to_coefficient(self, item.key().borrow(), item.value().uminus());
         }
         self
     }
 
-    fn add_scalar_mul(&mut self, other: &V, s: Self::ScalarType) -> &mut Self {
-        for (k, v) in other.iter_item() {
-            let tmp = ScalarField::<U>::mul(&s, v);
-            if let Some(curr) = self.get_mut(k) {
-                ScalarField::<U>::add_inplace(curr, &tmp);
-            } else {
-                self.insert_single(k, tmp);
-            }
+    fn add_scalar_mul(&mut self, other: &'vec2 V, s: Self::ScalarType) -> &mut Self {
+        for item in other {
+            add_to_coefficient(self, item.key().borrow(), item.value().mul(&s));
         }
         self
     }
 
-    fn sub_scalar_mul(&mut self, other: &V, s: Self::ScalarType) -> &mut Self {
-        for (k, v) in other.iter_item() {
-            let tmp = ScalarField::<U>::mul(&s, v);
-            if let Some(curr) = self.get_mut(k) {
-                ScalarField::<U>::sub_inplace(curr, &tmp);
-            } else {
-                self.insert_single(k, ScalarField::<U>::uminus(&tmp));
-            }
+    fn sub_scalar_mul(&mut self, other: &'vec2 V, s: Self::ScalarType) -> &mut Self {
+        for item in other {
+            add_to_coefficient(self, item.key().borrow(), item.value().mul(&s).uminus());
         }
         self
     }
 
-    fn add_scalar_div(&mut self, other: &V, s: Self::RationalType) -> &mut Self {
-        for (k, v) in other.iter_item() {
-            let tmp = ScalarField::<U>::div( v, &s);
-            if let Some(curr) = self.get_mut(k) {
-                ScalarField::<U>::add_inplace(curr, &tmp);
-            } else {
-                self.insert_single(k, tmp);
-            }
+    fn add_scalar_div(&mut self, other: &'vec2 V, s: <Self::ScalarType as CoefficientField>::RationalType) -> &mut Self
+        where Self::ScalarType: CoefficientField
+    {
+        for item in other {
+            add_to_coefficient(self, item.key().borrow(), item.value().div(&s));
         }
         self
     }
 
-    fn sub_scalar_div(&mut self, other: &V, s: Self::RationalType) -> &mut Self {
-        for (k, v) in other.iter_item() {
-            let tmp = ScalarField::<U>::div( v, &s);
-            if let Some(curr) = self.get_mut(k) {
-                ScalarField::<U>::sub_inplace(curr, &tmp);
-            } else {
-                self.insert_single(k, ScalarField::<U>::uminus(&tmp));
-            }
+    fn sub_scalar_div(&mut self, other: &'vec2 V, s: <Self::ScalarType as CoefficientField>::RationalType) -> &mut Self
+        where Self::ScalarType: CoefficientField
+    {
+        for item in other {
+            add_to_coefficient(self, item.key().borrow(), item.value().div(&s).uminus());
         }
         self
     }
 }
-*/
\ No newline at end of file
+
+// The sum is stored with insert_single, which drops coefficients that cancel to zero from
+// sparse vectors.
+fn add_to_coefficient<'vec, U: Vector<'vec>>(vector: &mut U, key: &U::KeyType, val: U::ScalarType)
+{
+    let sum = match vector.get(key) {
+        Some(v) => v.add(&val),
+        None => val
+    };
+    vector.insert_single(key, sum);
+}
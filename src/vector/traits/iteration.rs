@@ -1,5 +1,6 @@
 #![feature(generic_associated_types)]
 
+use std::borrow::Borrow;
 use std::iter::Iterator;
 use std::ops::{Deref,DerefMut};
 
@@ -9,9 +10,14 @@ use crate::coefficients::CoefficientField;
 use crate::vector::Vector;
 
 
+/// Item produced by iterating over a reference to a vector.
+///
+/// Sparse vectors store their keys and so can hand out references to them, whereas dense
+/// vectors compute each key from its index. The key item is therefore only required to borrow
+/// as a key, which covers both `&K` and `K`.
 pub trait VectorIteratorItem<'vec, K, S>
 {
-    type KeyItem: 'vec + Deref<Target=K>;
+    type KeyItem: 'vec + Borrow<K>;
     type ValueItem: 'vec + Deref<Target=S>;
 
     fn key(&self) -> Self::KeyItem;
@@ -21,7 +27,7 @@ pub trait VectorIteratorItem<'vec, K, S>
 
 pub trait VectorIteratorMutItem<'vec, K, S>
 {
-    type KeyItem: 'vec + Deref<Target=K>;
+    type KeyItem: 'vec + Borrow<K>;
     type ValueItem: 'vec + DerefMut<Target=S>;
 
     fn key(&self) -> Self::KeyItem;
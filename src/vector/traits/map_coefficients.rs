@@ -0,0 +1,23 @@
+
+use crate::coefficients::{CoefficientRing, ToCoefficient};
+
+use super::Vector;
+
+
+/// Conversion of a vector into a vector over the same basis with a different coefficient type.
+///
+/// The output vector has the same storage layout as the input, so the conversion is a single
+/// pass over the coefficients that does not need to look up any keys.
+pub trait MapCoefficients<'vec, T: 'static + CoefficientRing> : Vector<'vec>
+{
+    type Output: Vector<'vec, BasisType=Self::BasisType, KeyType=Self::KeyType, ScalarType=T>;
+
+    fn map_coefficients(&self, func: impl FnMut(&Self::ScalarType) -> T) -> Self::Output;
+
+    fn convert_coefficients(&self) -> Self::Output
+        where Self::ScalarType: ToCoefficient<T>
+    {
+        self.map_coefficients(ToCoefficient::to_coefficient)
+    }
+
+}
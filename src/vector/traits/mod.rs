@@ -6,8 +6,11 @@
 pub use vector::{Vector};
 pub use dense_vector::{DenseVector, ResizeableDenseVector};
 pub use sparse_vector::SparseVector;
-pub use extension::VectorKeyExt;
-pub use degree_vector::VectorWithDegree;
+pub use extension::{CrossTypeVectorExt, VectorKeyExt};
+pub use degree_vector::{DegreeProjection, VectorWithDegree};
+pub use approx_eq::VectorApproxEq;
+pub use map_coefficients::MapCoefficients;
+pub use normed_vector::{GradedNormedVector, Norm, NormedVector};
 //pub use iteration::{VectorIterItem, VectorIter};
 
 pub use iteration::{VectorIteratorItem, VectorIteratorMutItem, IntoVectorIterator};
@@ -19,5 +22,8 @@ mod vector;
 mod extension;
 mod degree_vector;
 mod iteration;
+mod approx_eq;
+mod map_coefficients;
+mod normed_vector;
 
 
@@ -0,0 +1,82 @@
+use crate::DegreeType;
+use crate::basis::BasisWithDegree;
+use crate::coefficients::Magnitude;
+
+use super::{Vector, VectorWithDegree};
+
+
+/// The norms available for vectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Norm {
+    /// Sum of the magnitudes of the coefficients.
+    L1,
+    /// Square root of the sum of the squared magnitudes of the coefficients.
+    L2,
+    /// Largest magnitude of any coefficient.
+    LInf
+}
+
+impl Norm {
+
+    /// Compute the norm of a collection of coefficients.
+    ///
+    /// Coefficients that are not present count as zero, so this gives the norm of any vector
+    /// whose non-zero coefficients are among the given values.
+    pub fn of_coefficients<'a, S: 'a + Magnitude>(self, coeffs: impl IntoIterator<Item=&'a S>) -> f64
+    {
+        let magnitudes = coeffs.into_iter().map(Magnitude::magnitude);
+        match self {
+            Norm::L1 => magnitudes.sum(),
+            Norm::L2 => magnitudes.map(|m| m * m).sum::<f64>().sqrt(),
+            Norm::LInf => magnitudes.fold(0.0, f64::max)
+        }
+    }
+}
+
+
+/// Vectors with norms and an inner product.
+///
+/// The inner product is the canonical bilinear form for which the basis is orthonormal. It does
+/// not conjugate either argument, so for complex coefficients it is not the Hermitian product.
+pub trait NormedVector<'vec> : Vector<'vec>
+    where <Self as Vector<'vec>>::ScalarType: Magnitude
+{
+
+    fn norm(&self, norm: Norm) -> f64;
+
+    fn l1_norm(&self) -> f64
+    {
+        self.norm(Norm::L1)
+    }
+
+    fn l2_norm(&self) -> f64
+    {
+        self.norm(Norm::L2)
+    }
+
+    fn linf_norm(&self) -> f64
+    {
+        self.norm(Norm::LInf)
+    }
+
+    fn inner_product(&self, other: &Self) -> Self::ScalarType;
+
+}
+
+
+/// Norms of the homogeneous components of a graded vector.
+pub trait GradedNormedVector<'vec> : NormedVector<'vec> + VectorWithDegree<'vec>
+    where <Self as Vector<'vec>>::ScalarType: Magnitude,
+          <Self as Vector<'vec>>::BasisType: BasisWithDegree
+{
+
+    /// Norm of the component of the vector of the given degree.
+    fn degree_norm(&self, degree: DegreeType, norm: Norm) -> f64;
+
+    /// Norms of the components of each degree, from zero up to the degree of the vector.
+    fn degree_norms(&self, norm: Norm) -> Vec<f64>
+    {
+        (0..=self.degree()).map(|deg| self.degree_norm(deg, norm)).collect()
+    }
+
+}
@@ -4,7 +4,7 @@ use std::borrow::{Borrow, BorrowMut};
 
 
 use crate::basis::{Basis};
-use crate::coefficients::CoefficientField;
+use crate::coefficients::{CoefficientRing, CoefficientField};
 //use super::iteration::VectorIterItem;
 //use super::VectorIter;
 
@@ -14,8 +14,7 @@ pub trait Vector<'vec> : 'vec + Sized + PartialEq /* + VectorIter<Self::KeyType,
 {
     type BasisType: 'static + Basis;
     type KeyType: 'static;
-    type ScalarType: 'static +  CoefficientField;
-    type RationalType: 'static;
+    type ScalarType: 'static + CoefficientRing;
 
     // Creation methods
     fn new() -> Self;
@@ -63,7 +62,9 @@ pub trait Vector<'vec> : 'vec + Sized + PartialEq /* + VectorIter<Self::KeyType,
 
     fn scalar_rmultiply(&self, scalar: impl Into<Self::ScalarType>) -> Self
     {
-        self.scalar_lmultiply(scalar)
+        let mut result = self.to_owned();
+        result.borrow_mut().scalar_rmultiply_inplace(scalar);
+        result
     }
     fn scalar_lmultiply(&self, scalar: impl Into<Self::ScalarType>) -> Self
     {
@@ -72,16 +73,20 @@ pub trait Vector<'vec> : 'vec + Sized + PartialEq /* + VectorIter<Self::KeyType,
         result
     }
 
-    fn scalar_rdivide(&self, rational: impl Into<Self::RationalType>) -> Self
+    fn scalar_rdivide(&self, rational: impl Into<<Self::ScalarType as CoefficientField>::RationalType>) -> Self
+        where Self::ScalarType: CoefficientField
     {
         let mut result = self.to_owned();
         result.borrow_mut().scalar_rdivide_inplace(rational.into());
         result
     }
 
-    fn scalar_ldivide(&self, rational: impl Into<Self::RationalType>) -> Self
+    fn scalar_ldivide(&self, rational: impl Into<<Self::ScalarType as CoefficientField>::RationalType>) -> Self
+        where Self::ScalarType: CoefficientField
     {
-        self.scalar_rdivide(rational)
+        let mut result = self.to_owned();
+        result.borrow_mut().scalar_ldivide_inplace(rational.into());
+        result
     }
 
     // Inplace binary operations acting on self.
@@ -90,27 +95,47 @@ pub trait Vector<'vec> : 'vec + Sized + PartialEq /* + VectorIter<Self::KeyType,
     fn add_inplace(&mut self, other: impl Borrow<Self>) -> &mut Self;
     fn sub_inplace(&mut self, other: impl Borrow<Self>) -> &mut Self;
 
+    // Scalars need not commute, so left and right multiplication are implemented separately.
     fn scalar_lmultiply_inplace(&mut self, scalar: impl Into<Self::ScalarType>) -> &mut Self;
-    fn scalar_rmultiply_inplace(&mut self, scalar: impl Into<Self::ScalarType>) -> &mut Self
+    fn scalar_rmultiply_inplace(&mut self, scalar: impl Into<Self::ScalarType>) -> &mut Self;
+
+    // Division is only available when the scalars form a field. The divisor is a rational, which
+    // commutes with every scalar, so left and right division agree. Implementations override
+    // scalar_rdivide_inplace_impl rather than these methods.
+    fn scalar_rdivide_inplace(&mut self, rational: impl Into<<Self::ScalarType as CoefficientField>::RationalType>) -> &mut Self
+        where Self::ScalarType: CoefficientField
     {
-        self.scalar_lmultiply_inplace(scalar)
+        self.scalar_rdivide_inplace_impl(&rational.into())
+    }
+    fn scalar_ldivide_inplace(&mut self, rational: impl Into<<Self::ScalarType as CoefficientField>::RationalType>) -> &mut Self
+        where Self::ScalarType: CoefficientField
+    {
+        self.scalar_rdivide_inplace_impl(&rational.into())
     }
 
-    fn scalar_rdivide_inplace(&mut self, rational: impl Into<Self::RationalType>) -> &mut Self;
-    fn scalar_ldivide_inplace(&mut self, rational: impl Into<Self::RationalType>) -> &mut Self
+    /// Divide every coefficient by `rational`.
+    ///
+    /// The default multiplies by the inverse of `rational`, since it has no access to the
+    /// coefficients. Implementations should override it to divide each coefficient.
+    fn scalar_rdivide_inplace_impl(&mut self, rational: &<Self::ScalarType as CoefficientField>::RationalType) -> &mut Self
+        where Self::ScalarType: CoefficientField
     {
-        self.borrow_mut().scalar_rdivide_inplace(rational)
+        self.scalar_rmultiply_inplace(<Self::ScalarType as CoefficientField>::inv(rational))
     }
 
 
     // Some utility functions that can be optimised for specific cases but should always be implemented
+    // The defaults of add_scalar_rmultiply and add_scalar_lmultiply build a temporary, so
+    // implementations should override them. The remaining methods are expressed in terms of
+    // these two and need not be overridden.
+
     /// Efficient implementation of self += (other * s)
     fn add_scalar_rmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self
     {
         self.add_inplace(other.borrow().scalar_rmultiply(scalar))
     }
 
-    /// Efficient implementation of self += (other * s)
+    /// Efficient implementation of self += (s * other)
     fn add_scalar_lmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self
     {
         self.add_inplace(other.borrow().scalar_lmultiply(scalar))
@@ -119,37 +144,73 @@ pub trait Vector<'vec> : 'vec + Sized + PartialEq /* + VectorIter<Self::KeyType,
     /// Efficient implementation of self -= (other * s)
     fn sub_scalar_rmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self
     {
-        self.sub_inplace(other.borrow().scalar_rmultiply(scalar))
+        self.add_scalar_rmultiply(other, scalar.into().uminus())
     }
 
-    /// Efficient implementation of self -= (other * s)
+    /// Efficient implementation of self -= (s * other)
     fn sub_scalar_lmultiply(&mut self, other: impl Borrow<Self>, scalar: impl Into<Self::ScalarType>) -> &mut Self
     {
-        self.sub_inplace(other.borrow().scalar_lmultiply(scalar))
+        self.add_scalar_lmultiply(other, scalar.into().uminus())
     }
 
     /// Efficient implementation of self += (other / s)
-    fn add_scalar_rdivide(&mut self, other: impl Borrow<Self>, rational: impl Into<Self::RationalType>) -> &mut Self
+    fn add_scalar_rdivide(&mut self, other: impl Borrow<Self>, rational: impl Into<<Self::ScalarType as CoefficientField>::RationalType>) -> &mut Self
+        where Self::ScalarType: CoefficientField
     {
-        self.add_inplace(other.borrow().scalar_rdivide(rational))
+        self.add_scalar_rdivide_impl(other.borrow(), &rational.into())
     }
 
     /// Efficient implementation of self += ((1/s)*other)
-    fn add_scalar_ldivide(&mut self, other: impl Borrow<Self>, rational: impl Into<Self::RationalType>) -> &mut Self
+    fn add_scalar_ldivide(&mut self, other: impl Borrow<Self>, rational: impl Into<<Self::ScalarType as CoefficientField>::RationalType>) -> &mut Self
+        where Self::ScalarType: CoefficientField
     {
-        self.add_inplace(other.borrow().scalar_ldivide(rational))
+        self.add_scalar_rdivide_impl(other.borrow(), &rational.into())
     }
 
     /// Efficient implementation of self -= (other / s)
-    fn sub_scalar_rdivide(&mut self, other: impl Borrow<Self>, rational: impl Into<Self::RationalType>) -> &mut Self
+    fn sub_scalar_rdivide(&mut self, other: impl Borrow<Self>, rational: impl Into<<Self::ScalarType as CoefficientField>::RationalType>) -> &mut Self
+        where Self::ScalarType: CoefficientField
     {
-        self.sub_inplace(other.borrow().scalar_rdivide(rational))
+        self.sub_scalar_rdivide_impl(other.borrow(), &rational.into())
     }
 
     /// Efficient implementation of self -= ((1/s)*other)
-    fn sub_scalar_ldivide(&mut self, other: impl Borrow<Self>, rational: impl Into<Self::RationalType>) -> &mut Self
+    fn sub_scalar_ldivide(&mut self, other: impl Borrow<Self>, rational: impl Into<<Self::ScalarType as CoefficientField>::RationalType>) -> &mut Self
+        where Self::ScalarType: CoefficientField
+    {
+        self.sub_scalar_rdivide_impl(other.borrow(), &rational.into())
+    }
+
+    /// Add `other` divided by `rational` to self. The default builds a temporary, so
+    /// implementations should override it.
+    fn add_scalar_rdivide_impl(&mut self, other: &Self, rational: &<Self::ScalarType as CoefficientField>::RationalType) -> &mut Self
+        where Self::ScalarType: CoefficientField
+    {
+        let mut quotient = other.to_owned();
+        quotient.scalar_rdivide_inplace_impl(rational);
+        self.add_inplace(quotient)
+    }
+
+    /// Subtract `other` divided by `rational` from self. The default builds a temporary, so
+    /// implementations should override it.
+    fn sub_scalar_rdivide_impl(&mut self, other: &Self, rational: &<Self::ScalarType as CoefficientField>::RationalType) -> &mut Self
+        where Self::ScalarType: CoefficientField
+    {
+        let mut quotient = other.to_owned();
+        quotient.scalar_rdivide_inplace_impl(rational);
+        self.sub_inplace(quotient)
+    }
+
+    /// Efficient implementation of self += sum(other * s) over the pairs in `terms`.
+    ///
+    /// This is how a `LinearCombination` is evaluated. Dense vectors override it to add all
+    /// of the terms in a single pass over their storage.
+    fn add_linear_combination(&mut self, terms: &[(&Self, Self::ScalarType)]) -> &mut Self
     {
-        self.sub_inplace(other.borrow().scalar_ldivide(rational))
+        for (other, scalar) in terms {
+            self.add_scalar_rmultiply(*other, scalar.clone());
+        }
+        self
     }
 
 }